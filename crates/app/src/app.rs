@@ -15,21 +15,78 @@ pub struct App {
 	canvas: EguiCanvas,
 
 	plot_axes: [[usize; 2]; NUM_PLOTS],
+	/// Plots showing the performance curves rather than the flight.
+	#[serde(default)]
+	plot_performance: [bool; NUM_PLOTS],
+	#[serde(default = "default_performance_axes")]
+	performance_axes: [[usize; 2]; NUM_PLOTS],
+	#[serde(skip)]
+	performance: Option<(Performance, Plotter)>,
+	#[serde(skip)]
+	stability: Option<Stability>,
+	/// Preset of the aircraft being flown (by name), for the defaults of the design panel.
+	#[serde(skip)]
+	preset: Option<(String, Plane)>,
 }
 
 const NUM_PLOTS: usize = 3;
 
-#[derive(Serialize, Deserialize, Default, Debug, EguiInspect)]
+#[derive(Serialize, Deserialize, Debug, EguiInspect)]
+#[serde(default)]
 struct UiState {
 	gamestate_open: bool,
 	scenegraph_open: bool,
 	commands_open: bool,
 	dark_mode: bool,
+	/// Aircraft file being edited in the design panel.
+	aircraft_toml: String,
+	aircraft_error: String,
+	/// Target of the trim solver: true airspeed (m/s) and climb angle (rad).
+	trim_airspeed: f32,
+	trim_climb_angle: f32,
+	trim_error: String,
+	stability_error: String,
+	/// Altitude for the performance sweep (m).
+	performance_altitude: f32,
+	performance_message: String,
+	flight_plan_error: String,
+	autoland_error: String,
+	/// World position where the canvas context menu was opened.
+	#[serde(skip)]
+	#[inspect(hide)]
+	context_menu_pos: Option<vec2f>,
+	/// Action waiting for a key press to be bound to.
+	#[serde(skip)]
+	#[inspect(hide)]
+	rebinding: Option<Action>,
+}
+
+impl Default for UiState {
+	fn default() -> Self {
+		Self {
+			gamestate_open: false,
+			scenegraph_open: false,
+			commands_open: false,
+			dark_mode: false,
+			aircraft_toml: String::new(),
+			aircraft_error: String::new(),
+			trim_airspeed: 65.0,
+			trim_climb_angle: 0.0,
+			trim_error: String::new(),
+			stability_error: String::new(),
+			performance_altitude: 0.0,
+			performance_message: String::new(),
+			flight_plan_error: String::new(),
+			autoland_error: String::new(),
+			context_menu_pos: None,
+			rebinding: None,
+		}
+	}
 }
 
 impl App {
 	pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-		let settings = Settings::todo_load_settings();
+		let settings = Settings::load();
 		let s = Self::try_restore(cc, &settings).unwrap_or_else(|| Self::default(cc, &settings));
 		// egui does not restore dark mode for some reason
 		if s.ui_state.dark_mode {
@@ -41,11 +98,19 @@ impl App {
 	fn default(cc: &eframe::CreationContext<'_>, settings: &Settings) -> Self {
 		Self {
 			canvas: EguiCanvas::new(cc, &settings.graphics),
-			gs: default(),
+			gs: GameState {
+				controls: settings.controls.clone(),
+				..default()
+			},
 			ui_state: UiState::default(),
 			scenegraph: default(),
 			update_scenegraph: true,
 			plot_axes: [[1, 2], [0, 1], [0, 2]],
+			plot_performance: default(),
+			performance_axes: default_performance_axes(),
+			performance: None,
+			stability: None,
+			preset: None,
 		}
 	}
 
@@ -56,9 +121,10 @@ impl App {
 
 		// deserialize from base64(zip(rmp))
 		let enc64 = storage.get_string(eframe::APP_KEY)?;
-		let restored = rmp_serde::from_read(GzDecoder::new(base64::read::DecoderReader::new(io::Cursor::new(enc64), &BASE64_STANDARD)))
+		let mut restored: Self = rmp_serde::from_read(GzDecoder::new(base64::read::DecoderReader::new(io::Cursor::new(enc64), &BASE64_STANDARD)))
 			.inspect_err(|e| log::error!("{e:#}"))
 			.ok()?;
+		restored.gs.controls = settings.controls.clone();
 
 		Some(Self {
 			canvas: EguiCanvas::new(cc, &settings.graphics), // 👈 hack to initialize wgpu resources
@@ -68,6 +134,8 @@ impl App {
 
 	fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
 		self.gs.tick();
+		// set before the panels: a key bound this frame reaches the game state in the next frame, and must not fly
+		self.gs.keys_captured = self.ui_state.rebinding.is_some();
 
 		self.top_panel(ctx);
 		self.left_panel(ctx);
@@ -102,14 +170,200 @@ impl App {
 
 	fn right_panel(&mut self, ctx: &egui::Context) {
 		egui::SidePanel::right("right_panel").min_width(320.0).show(ctx, |ui| {
+			let t = self.gs.time();
 			let plane = &mut self.gs.plane;
-			let default = Plane::default();
+			let default = preset_default(&mut self.preset, &plane.name);
 
 			ui.heading("controls");
 			let a = 30.0 * DEG;
-			slider(ui, "◀ ▶throttle", "N", 0.0..=(plane.max_propeller_force), &mut plane.propeller_force);
-			slider(ui, "↕elevator", "rad", -a..=a, &mut plane.elevator.pitch);
-			ui.label("(or use arrow keys)");
+			let (throttle, pitch) = (plane.engine.throttle, plane.controls.pitch);
+			slider(ui, "◀ ▶throttle", "", 0.0..=1.0, &mut plane.engine.throttle);
+			slider(ui, "↕pitch", "", -1.0..=1.0, &mut plane.controls.pitch);
+			slider(ui, "↕pitch trim", "", -1.0..=1.0, &mut plane.controls.pitch_trim);
+			self.gs.autoland.pilot_input(plane.controls.pitch != pitch, plane.engine.throttle != throttle, &mut self.gs.autopilot);
+			slider(ui, "flaps", "", 0.0..=1.0, &mut plane.controls.flaps);
+			slider(ui, "spoilers", "", 0.0..=1.0, &mut plane.controls.spoilers);
+			ui.horizontal(|ui| {
+				ui.toggle_value(&mut plane.controls.parking_brake, "🅿 parking brake");
+				if plane.gear.is_retractable() {
+					ui.toggle_value(&mut plane.gear.down_selected, "gear down");
+				}
+			});
+			ui.collapsing("⌨keys", |ui| {
+				let controls = &mut self.gs.controls;
+				let defaults = Controls::default();
+				set_quantity(ui, "pitch rate", "/s", 0.1..=10.0, 0.01, defaults.pitch_rate, &mut controls.pitch_rate);
+				set_quantity(ui, "pitch centering", "/s", 0.0..=10.0, 0.01, defaults.pitch_centering, &mut controls.pitch_centering);
+				set_quantity(ui, "throttle rate", "/s", 0.1..=10.0, 0.01, defaults.throttle_rate, &mut controls.throttle_rate);
+				set_quantity(ui, "trim rate", "/s", 0.01..=10.0, 0.01, defaults.trim_rate, &mut controls.trim_rate);
+				egui::Grid::new("key_bindings").show(ui, |ui| {
+					for action in Action::ALL {
+						ui.label(action.label());
+						ui.horizontal(|ui| {
+							for key in controls.keys(action).to_vec() {
+								if ui.button(key.symbol_or_name()).on_hover_text("remove").clicked() {
+									controls.unbind(action, key);
+								}
+							}
+							if self.ui_state.rebinding == Some(action) {
+								ui.label("press a key (Esc: cancel)");
+							} else if ui.small_button("➕").on_hover_text("add a key").clicked() {
+								self.ui_state.rebinding = Some(action);
+							}
+						});
+						ui.end_row();
+					}
+				});
+				if let Some(action) = self.ui_state.rebinding {
+					let pressed = ui.input(|i| {
+						i.events.iter().find_map(|e| match e {
+							egui::Event::Key { key, pressed: true, .. } => Some(*key),
+							_ => None,
+						})
+					});
+					match pressed {
+						Some(egui::Key::Escape) => self.ui_state.rebinding = None,
+						Some(key) => {
+							controls.bind(action, key);
+							self.ui_state.rebinding = None;
+						}
+						None => (),
+					}
+				}
+				if controls.bindings != defaults.bindings && ui.button("❌ reset keys").clicked() {
+					controls.bindings = defaults.bindings;
+				}
+			});
+			ui.collapsing("🤖autopilot", |ui| {
+				let autopilot = &mut self.gs.autopilot;
+				ui.horizontal(|ui| {
+					for (mode, label) in [(PitchMode::Pitch, "PITCH"), (PitchMode::Altitude, "ALT"), (PitchMode::VerticalSpeed, "V/S")] {
+						if ui.selectable_label(autopilot.pitch_mode == mode, label).clicked() {
+							match autopilot.pitch_mode == mode {
+								true => autopilot.pitch_mode = PitchMode::Off,
+								false => autopilot.engage(mode, plane),
+							}
+						}
+					}
+					if ui.selectable_label(autopilot.autothrottle, "A/THR").clicked() {
+						match autopilot.autothrottle {
+							true => autopilot.autothrottle = false,
+							false => autopilot.engage_autothrottle(plane),
+						}
+					}
+					if autopilot.is_engaged() && ui.button("disconnect").clicked() {
+						autopilot.pitch_mode = PitchMode::Off;
+						autopilot.autothrottle = false;
+					}
+				});
+				set_angle(ui, "pitch", -(30.0 * DEG)..=(30.0 * DEG), 0.0, &mut autopilot.target_pitch);
+				set_quantity(ui, "altitude", "m", 0.0..=10000.0, 1.0, 500.0, &mut autopilot.target_altitude);
+				set_quantity(ui, "vertical speed", "m/s", -20.0..=20.0, 0.1, 0.0, &mut autopilot.target_vertical_speed);
+				set_quantity(ui, "airspeed (IAS)", "m/s", 0.0..=200.0, 0.1, 65.0, &mut autopilot.target_airspeed);
+				ui.label("(pilot input disconnects)");
+				egui::CollapsingHeader::new("gains").show(ui, |ui| {
+					autopilot.max_vertical_speed.inspect_mut("max vertical speed (m/s)", ui);
+					set_angle(ui, "max pitch", 0.0..=(45.0 * DEG), 15.0 * DEG, &mut autopilot.max_pitch);
+					autopilot.pitch_pid.inspect_mut("pitch (rad → input)", ui);
+					autopilot.vertical_speed_pid.inspect_mut("vertical speed (m/s → rad)", ui);
+					autopilot.altitude_pid.inspect_mut("altitude (m → m/s)", ui);
+					autopilot.airspeed_pid.inspect_mut("airspeed (m/s → throttle)", ui);
+				});
+			});
+			ui.collapsing("🗺flight plan", |ui| {
+				let plan = &mut self.gs.flight_plan;
+				ui.horizontal(|ui| {
+					if ui.button("▶ fly").on_hover_text("follow the plan with the autopilot").clicked() {
+						match plan.engage(&mut self.gs.autopilot, plane) {
+							Ok(()) => self.ui_state.flight_plan_error.clear(),
+							Err(e) => self.ui_state.flight_plan_error = format!("{e:#}"),
+						}
+					}
+					if ui.button("❌ clear").clicked() {
+						plan.waypoints.clear();
+					}
+				});
+				if !self.ui_state.flight_plan_error.is_empty() {
+					ui.colored_label(egui::Color32::RED, &self.ui_state.flight_plan_error);
+				}
+				let active = plan.active(plane.position().x());
+				let mut remove = None;
+				for (i, w) in plan.waypoints.iter_mut().enumerate() {
+					ui.horizontal(|ui| {
+						let label = format!("{}{}", if active == Some(i) { "▶" } else { "" }, i + 1);
+						ui.label(label);
+						ui.add(egui::DragValue::new(&mut w.position[0]).speed(10.0).suffix(" m"));
+						ui.add(egui::DragValue::new(&mut w.position[1]).speed(1.0).suffix(" m alt"));
+						ui.add(egui::DragValue::new(&mut w.airspeed).speed(0.1).clamp_range(0.0..=200.0).suffix(" m/s"));
+						if ui.button("❌").clicked() {
+							remove = Some(i);
+						}
+					});
+				}
+				if let Some(i) = remove {
+					plan.waypoints.remove(i);
+				}
+				plan.sort();
+				ui.label("(right-click the world to add waypoints)");
+				set_angle(ui, "climb angle", 0.0..=(15.0 * DEG), 2.0 * DEG, &mut plan.climb_angle);
+				set_angle(ui, "descent angle", 0.0..=(15.0 * DEG), 1.5 * DEG, &mut plan.descent_angle);
+			});
+			ui.collapsing("🛬autoland", |ui| {
+				let autoland = &mut self.gs.autoland;
+				ui.horizontal(|ui| {
+					ui.label("touchdown x");
+					ui.add(egui::DragValue::new(&mut autoland.touchdown[0]).speed(10.0).suffix(" m"));
+					if ui.button("current").on_hover_text("1 km ahead").clicked() {
+						autoland.touchdown[0] = plane.position().x() + 1000.0;
+					}
+				});
+				set_angle(ui, "glide slope", (1.0 * DEG)..=(10.0 * DEG), 3.0 * DEG, &mut autoland.glide_slope);
+				set_quantity(ui, "approach speed (IAS)", "m/s", 10.0..=200.0, 0.1, 60.0, &mut autoland.approach_speed);
+				set_quantity(ui, "flare height", "m", 1.0..=50.0, 0.1, 10.0, &mut autoland.flare_height);
+				set_quantity(ui, "touchdown sink rate", "m/s", 0.0..=3.0, 0.01, 0.5, &mut autoland.touchdown_sink_rate);
+				ui.horizontal(|ui| {
+					if ui.button("🛬 land").on_hover_text("approach, flare and stop at the touchdown point").clicked() {
+						let x = autoland.touchdown.x();
+						match autoland.engage(vec2(x, self.gs.tilemap.terrain_height(x)), plane, &mut self.gs.autopilot) {
+							Ok(()) => self.ui_state.autoland_error.clear(),
+							Err(e) => self.ui_state.autoland_error = format!("{e:#}"),
+						}
+					}
+					if let Some(error) = autoland.touchdown_error {
+						ui.label(format!("touched down {error:+.0} m from the aim point"));
+					}
+				});
+				if !self.ui_state.autoland_error.is_empty() {
+					ui.colored_label(egui::Color32::RED, &self.ui_state.autoland_error);
+				}
+			});
+			ui.collapsing("⚖trim", |ui| {
+				set_quantity(ui, "airspeed (TAS)", "m/s", 1.0..=200.0, 0.1, 65.0, &mut self.ui_state.trim_airspeed);
+				set_angle(ui, "climb angle", -(45.0 * DEG)..=(45.0 * DEG), 0.0, &mut self.ui_state.trim_climb_angle);
+				ui.horizontal(|ui| {
+					if ui.button("current").on_hover_text("use the current airspeed and climb angle").clicked() {
+						let v = plane.air_velocity();
+						self.ui_state.trim_airspeed = v.len();
+						self.ui_state.trim_climb_angle = f32::atan2(v.y(), v.x());
+					}
+					if ui
+						.button("trim here")
+						.on_hover_text("steady flight at this airspeed and climb angle, from the current position")
+						.clicked()
+					{
+						match plane.trim(self.ui_state.trim_airspeed, self.ui_state.trim_climb_angle) {
+							Ok(trim) => {
+								plane.set_trim(&trim);
+								self.ui_state.trim_error.clear();
+							}
+							Err(e) => self.ui_state.trim_error = format!("{e:#}"),
+						}
+					}
+				});
+				if !self.ui_state.trim_error.is_empty() {
+					ui.colored_label(egui::Color32::RED, &self.ui_state.trim_error);
+				}
+			});
 			ui.horizontal(|ui| {
 				ui.toggle_value(&mut self.gs.debug.pause_all_systems, "pause");
 				if ui.button("❌ reset").clicked() {
@@ -117,11 +371,20 @@ impl App {
 					plane.body.rot_velocity = 0.0;
 					plane.body.rotation = 0.0;
 					plane.body.position = Plane::default().body.position;
+					self.gs.crash_detector = CrashDetector::default();
 					self.gs.plotter.clear();
 					self.gs.frame = 0;
 				}
 			});
 
+			ui.heading("📋events");
+			for event in self.gs.events.iter().rev().take(5) {
+				event_label(ui, event);
+			}
+			if !self.gs.events.is_empty() && ui.button("clear").clicked() {
+				self.gs.events.clear();
+			}
+
 			ui.heading("kinematics");
 			inspect_vec2(ui, "position", "m", 1, &mut plane.body.position);
 			inspect_vec2(ui, "velocity", "m/s", 1, &mut plane.body.velocity);
@@ -131,39 +394,309 @@ impl App {
 			inspect_value(ui, "rot. accell", "rad/s²", 2, &mut plane.body.rot_accel);
 
 			ui.heading("✈aircraft design");
-			set_quantity(ui, "mass", "kg", 0.0..=10000.0, 10.0, default.body.mass, &mut plane.body.mass);
-			set_quantity(ui, "rot. intertia", "kg m²", 0.0..=10000.0, 10.0, default.body.rot_inertia, &mut plane.body.rot_inertia);
+			ui.horizontal_wrapped(|ui| {
+				for preset in &AIRCRAFT_PRESETS {
+					if ui.selectable_label(plane.name == preset.name, preset.name).clicked() {
+						match preset.load() {
+							Ok(new) => {
+								*plane = new;
+								self.gs.crash_detector = CrashDetector::default();
+								self.gs.plotter.clear();
+								self.gs.frame = 0;
+							}
+							Err(e) => self.ui_state.aircraft_error = format!("{e:#}"),
+						}
+					}
+				}
+			});
+			ui.label(&plane.description);
+			egui::CollapsingHeader::new("📄aircraft file").show(ui, |ui| {
+				ui.horizontal(|ui| {
+					if ui.button("⬇ from aircraft").on_hover_text("show the current design as TOML").clicked() {
+						match AircraftFile::from_plane(plane).to_toml() {
+							Ok(toml) => self.ui_state.aircraft_toml = toml,
+							Err(e) => self.ui_state.aircraft_error = format!("{e:#}"),
+						}
+					}
+					if ui.button("📋 copy").clicked() {
+						ui.output_mut(|o| o.copied_text = self.ui_state.aircraft_toml.clone());
+					}
+					if ui.button("✈ load").on_hover_text("replace the aircraft by this design").clicked() {
+						match AircraftFile::parse(&self.ui_state.aircraft_toml) {
+							Ok(file) => {
+								*plane = file.to_plane();
+								self.ui_state.aircraft_error.clear();
+							}
+							Err(e) => self.ui_state.aircraft_error = format!("{e:#}"),
+						}
+					}
+				});
+				if !self.ui_state.aircraft_error.is_empty() {
+					ui.colored_label(egui::Color32::RED, &self.ui_state.aircraft_error);
+				}
+				egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+					ui.add(egui::TextEdit::multiline(&mut self.ui_state.aircraft_toml).code_editor().desired_width(f32::INFINITY));
+				});
+			});
+			egui::CollapsingHeader::new("📈stability").show(ui, |ui| {
+				let (airspeed, climb_angle) = (self.ui_state.trim_airspeed, self.ui_state.trim_climb_angle);
+				let hover = format!("linearize around steady flight at {airspeed:.1} m/s, climb angle {:.1}° (see ⚖trim)", climb_angle / DEG);
+				if ui.button("compute").on_hover_text(hover).clicked() {
+					match plane.stability(airspeed, climb_angle) {
+						Ok(stability) => {
+							self.stability = Some(stability);
+							self.ui_state.stability_error.clear();
+						}
+						Err(e) => {
+							self.stability = None;
+							self.ui_state.stability_error = format!("{e:#}");
+						}
+					}
+				}
+				if !self.ui_state.stability_error.is_empty() {
+					ui.colored_label(egui::Color32::RED, &self.ui_state.stability_error);
+				}
+				if let Some(stability) = &self.stability {
+					let trim = &stability.trim;
+					ui.label(format!("trimmed at {:.1} m/s, climb angle {:.1}°", trim.airspeed, trim.climb_angle / DEG));
+					ui.label(format!(
+						"throttle {:.0}% | pitch {:+.1}° | pitch input {:+.2}",
+						100.0 * trim.throttle,
+						trim.pitch / DEG,
+						trim.pitch_input
+					));
+					for mode in &stability.modes {
+						let color = if mode.is_stable() { ui.visuals().text_color() } else { egui::Color32::RED };
+						ui.colored_label(color, mode.to_string());
+					}
+					ui.collapsing("state matrix", |ui| {
+						ui.label("[vx, vy, pitch, pitch rate]");
+						for row in &stability.matrix {
+							ui.monospace(row.iter().map(|v| format!("{v:+9.4}")).join(" "));
+						}
+					});
+				}
+			});
+			egui::CollapsingHeader::new("🚀performance").show(ui, |ui| {
+				set_quantity(ui, "altitude", "m", 0.0..=10000.0, 10.0, 0.0, &mut self.ui_state.performance_altitude);
+				ui.horizontal(|ui| {
+					if ui.button("compute").on_hover_text("sweep airspeed and altitude, in the current configuration").clicked() {
+						match plane.performance(&self.gs.atmosphere, self.ui_state.performance_altitude) {
+							Ok(performance) => {
+								let plotter = performance.plotter();
+								self.performance = Some((performance, plotter));
+								self.ui_state.performance_message.clear();
+							}
+							Err(e) => self.ui_state.performance_message = format!("{e:#}"),
+						}
+					}
+					if let Some((performance, _)) = &self.performance {
+						if ui.button("📋 copy CSV").clicked() {
+							ui.output_mut(|o| o.copied_text = performance.to_csv());
+						}
+						#[cfg(not(target_arch = "wasm32"))]
+						if ui.button("💾 save CSV").clicked() {
+							let file = format!("{}_performance.csv", plane.name.replace(' ', "_"));
+							self.ui_state.performance_message = match std::fs::write(&file, performance.to_csv()) {
+								Ok(()) => format!("saved {file}"),
+								Err(e) => format!("{file}: {e}"),
+							};
+						}
+					}
+				});
+				if !self.ui_state.performance_message.is_empty() {
+					ui.label(&self.ui_state.performance_message);
+				}
+				if let Some((p, _)) = &self.performance {
+					let or_none = |v: Option<f32>, unit: &str| v.map_or("-".into(), |v| format!("{v:.0} {unit}"));
+					ui.label(format!("at {:.0} m:", p.altitude));
+					ui.label(format!("stall speed: {:.0} m/s", p.stall_speed));
+					ui.label(format!("max level speed: {}", or_none(p.max_level_speed, "m/s")));
+					ui.label(format!("best climb: {:.1} m/s at {:.0} m/s", p.best_climb_rate, p.best_climb_speed));
+					ui.label(format!("best glide: {:.1} at {:.0} m/s", p.best_glide_ratio, p.best_glide_speed));
+					ui.label(format!("service ceiling: {}", or_none(p.service_ceiling, "m")));
+					ui.label("(plot the curves with 🚀 in the plot panel)");
+				}
+			});
+
 			set_quantity(ui, "gravity", "N/kg", 0.0..=10.0, 0.01, default.gravity, &mut plane.gravity);
 			set_quantity(ui, "fuselage drag", "N/√(m/s)", 0.0..=5.0, 0.001, default.body_drag, &mut plane.body_drag);
-			set_quantity(ui, "max propeller", "N", 0.0..=5000.0, 10.0, default.max_propeller_force, &mut plane.max_propeller_force);
 
-			ui.strong("✈wings");
+			ui.strong("⚙engine");
+			let engine = &mut plane.engine;
+			let mut max_power_kw = engine.max_power / 1e3;
+			set_quantity(ui, "max power", "kW", 0.0..=1000.0, 1.0, default.engine.max_power / 1e3, &mut max_power_kw);
+			engine.max_power = max_power_kw * 1e3;
+			set_quantity(ui, "static thrust", "N", 0.0..=10000.0, 10.0, default.engine.static_thrust, &mut engine.static_thrust);
+			set_quantity(ui, "prop efficiency", "", 0.0..=1.0, 0.01, default.engine.prop_efficiency, &mut engine.prop_efficiency);
+			set_quantity(ui, "prop max speed", "m/s", 1.0..=500.0, 1.0, default.engine.prop_max_speed, &mut engine.prop_max_speed);
+			ui.horizontal(|ui| {
+				ui.toggle_value(&mut plane.engine.failed, "💥 engine failure");
+				if ui.button("⛽ refuel").clicked() {
+					plane.refuel();
+				}
+			});
 
-			set_vec2(ui, "wings pos", "m", -10.0..=10.0, 0.01, default.wings.pos, &mut plane.wings.pos);
-			set_angle(ui, "wings pitch", -a..=a, default.wings.pitch, &mut plane.wings.pitch);
-			set_quantity(ui, "wings drag", "N/√(m/s)", 0.0..=10.0, 0.001, default.wings.drag_factor, &mut plane.wings.drag_factor);
-			set_quantity(ui, "wings l2d", "", 0.0..=20.0, 0.1, default.wings.lift_to_drag, &mut plane.wings.lift_to_drag);
+			ui.strong("⚖weight & balance");
+			for (station, default) in iter::zip(&mut plane.stations, &default.stations) {
+				let label = match station.kind {
+					StationKind::FuelTank => format!("{} capacity", station.name),
+					_ => station.name.clone(),
+				};
+				set_quantity(ui, &label, "kg", 0.0..=2000.0, 1.0, default.mass, &mut station.mass);
+			}
+			let props = plane.mass_properties();
+			ui.label(format!("total: {:.0} kg | inertia: {:.0} kg m²", props.mass, props.rot_inertia));
+			let wing_x = plane.main_wing().map_or(0.0, |w| w.pos.x());
+			ui.label(format!("CG: {:+.2} m ({:+.2} m ahead of wing)", props.cg.x(), props.cg.x() - wing_x));
+			ui.label(format!("limits: {:+.2} .. {:+.2} m", plane.cg_aft_limit, plane.cg_forward_limit));
+			if !plane.cg_in_limits() {
+				ui.colored_label(egui::Color32::RED, "CG OUT OF LIMITS");
+			}
+			if let Some(stability) = plane.static_stability() {
+				ui.label(format!(
+					"neutral point: {:+.2} m | static margin: {:+.2} m ({:+.0}% chord)",
+					stability.neutral_point,
+					stability.static_margin,
+					100.0 * stability.static_margin_chord
+				));
+				match stability.verdict() {
+					StabilityVerdict::Stable => ui.colored_label(egui::Color32::DARK_GREEN, "statically stable"),
+					StabilityVerdict::Neutral => ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "neutral: hard to hold pitch"),
+					StabilityVerdict::Unstable => ui.colored_label(egui::Color32::RED, "UNSTABLE: CG behind the neutral point"),
+				};
+			}
 
-			ui.strong("↕elevator");
-			set_vec2(ui, "elevator pos", "m", -10.0..=10.0, 0.01, default.elevator.pos, &mut plane.elevator.pos);
-			set_angle(ui, "elevator pitch", -a..=a, default.elevator.pitch, &mut plane.elevator.pitch);
-			set_quantity(ui, "elevator drag", "N/√(m/s)", 0.0..=5.0, 0.001, default.elevator.drag_factor, &mut plane.elevator.drag_factor);
-			set_quantity(ui, "elevator l2d", "", 0.0..=20.0, 0.1, default.elevator.lift_to_drag, &mut plane.elevator.lift_to_drag);
+			ui.strong("✈surfaces");
+			let mut remove = None;
+			for (i, surface) in plane.surfaces.iter_mut().enumerate() {
+				let default = default.surfaces.iter().find(|d| d.name == surface.name).cloned().unwrap_or_default();
+				egui::CollapsingHeader::new(format!("{} ({:?})", surface.name, surface.kind)).id_source(i).show(ui, |ui| {
+					ui.horizontal(|ui| {
+						ui.label("name");
+						ui.text_edit_singleline(&mut surface.name);
+					});
+					surface.kind.inspect_mut("kind", ui);
+					set_vec2(ui, "pos", "m", -10.0..=10.0, 0.01, default.pos, &mut surface.pos);
+					set_angle(ui, "pitch", -a..=a, default.pitch, &mut surface.pitch);
+					set_lift_curve(ui, "", 10.0, &default.curve, &mut surface.curve);
+					set_quantity(ui, "moment", "N s²/m", -5.0..=5.0, 0.01, default.pitching_moment, &mut surface.pitching_moment);
+					surface.control.inspect_mut("control", ui);
+					set_angle(ui, "max deflection", -(90.0 * DEG)..=(90.0 * DEG), default.max_deflection, &mut surface.max_deflection);
+					if surface.control != ControlAxis::Fixed {
+						let title = if surface.actuator.is_ideal() { "actuator (ideal)" } else { "actuator" };
+						egui::CollapsingHeader::new(title).id_source("actuator").show(ui, |ui| {
+							let (actuator, default) = (&mut surface.actuator, &default.actuator);
+							set_angle(ui, "lowest", -(90.0 * DEG)..=(90.0 * DEG), default.limits.x(), &mut actuator.limits[0]);
+							set_angle(ui, "highest", actuator.limits.x()..=(90.0 * DEG), default.limits.y(), &mut actuator.limits[1]);
+							set_quantity(ui, "max rate", "rad/s (0: unlimited)", 0.0..=100.0, 0.01, default.max_rate, &mut actuator.max_rate);
+							set_quantity(ui, "time constant", "s", 0.0..=5.0, 0.001, default.time_constant, &mut actuator.time_constant);
+							set_quantity(ui, "blowback speed", "m/s IAS (0: none)", 0.0..=300.0, 0.1, default.blowback_speed, &mut actuator.blowback_speed);
+						});
+					}
+					set_vec2(ui, "size", "m", 0.0..=10.0, 0.01, default.size, &mut surface.size);
+					if ui.button("🗑 remove").clicked() {
+						remove = Some(i);
+					}
+				});
+			}
+			if let Some(i) = remove {
+				plane.surfaces.remove(i);
+			}
+			if ui.button("➕ add surface").clicked() {
+				plane.surfaces.push(Winglet {
+					name: format!("surface {}", plane.surfaces.len() + 1),
+					..Winglet::default()
+				});
+			}
 
 			ui.strong("💿wheels");
 			set_vec2(ui, "wheel1 pos", "m", -10.0..=10.0, 0.01, default.wheels[0], &mut plane.wheels[0]);
 			set_vec2(ui, "wheel2 pos", "m", -10.0..=10.0, 0.01, default.wheels[1], &mut plane.wheels[1]);
+			set_quantity(ui, "max brake force", "N", 0.0..=50000.0, 10.0, default.tyre.max_brake_force, &mut plane.tyre.max_brake_force);
+			ui.horizontal(|ui| {
+				ui.label("retracts");
+				ui.checkbox(&mut plane.gear.retracts[0], "wheel1");
+				ui.checkbox(&mut plane.gear.retracts[1], "wheel2");
+			});
+			let (gear, default_gear) = (&mut plane.gear, &default.gear);
+			set_vec2(ui, "retracted offset", "m", -5.0..=5.0, 0.01, default_gear.retracted_offset, &mut gear.retracted_offset);
+			set_quantity(ui, "gear transition", "s", 0.1..=30.0, 0.1, default_gear.transition_time, &mut gear.transition_time);
+			set_quantity(ui, "gear drag", "N s²/m²", 0.0..=2.0, 0.001, default_gear.drag, &mut gear.drag);
+			set_quantity(ui, "max gear operating speed", "m/s", 0.0..=200.0, 1.0, default_gear.max_operating_speed, &mut gear.max_operating_speed);
+			set_quantity(ui, "max gear extended speed", "m/s", 0.0..=200.0, 1.0, default_gear.max_extended_speed, &mut gear.max_extended_speed);
+			ui.horizontal(|ui| {
+				ui.label("brakes on");
+				ui.checkbox(&mut plane.tyre.braked[0], "wheel1");
+				ui.checkbox(&mut plane.tyre.braked[1], "wheel2");
+			});
 
-			if ui.button("❌ reset design").clicked() {
-				*plane = Plane::default();
+			if ui.button("❌ reset design").on_hover_text("back to the bundled design").clicked() {
+				*plane = default.clone();
 			}
 
+			ui.heading("🌡atmosphere");
+			let atmosphere = &mut self.gs.atmosphere;
+			set_quantity(ui, "ISA deviation", "K", -50.0..=50.0, 0.1, 0.0, &mut atmosphere.temperature_offset);
+			set_quantity(ui, "QNH", "Pa", 90000.0..=110000.0, 10.0, Atmosphere::SEA_LEVEL_PRESSURE, &mut atmosphere.sea_level_pressure);
+			let air = &plane.air;
+			ui.label(format!("{:.1} °C | {:.0} hPa | {:.3} kg/m³", air.temperature - 273.15, air.pressure / 100.0, air.density));
+			ui.label(format!("density altitude: {:.0} m", Atmosphere::density_altitude(air.density)));
+
+			ui.heading("🌬wind");
+			let wind = &mut self.gs.wind;
+			let mut surface_wind = wind.steady_wind(10.0);
+			set_quantity(ui, "surface wind", "m/s", -30.0..=30.0, 0.1, 0.0, &mut surface_wind);
+			if surface_wind != wind.steady_wind(10.0) {
+				wind.layers = WindField::with_surface_wind(surface_wind).layers;
+			}
+			set_quantity(ui, "turbulence", "m/s", 0.0..=10.0, 0.1, 0.0, &mut wind.turbulence.intensity);
+			ui.horizontal(|ui| {
+				ui.label("seed");
+				ui.add(egui::DragValue::new(&mut wind.turbulence.seed));
+				if ui.button("restart").on_hover_text("restart turbulence from seed").clicked() {
+					wind.turbulence.reseed();
+				}
+			});
+			set_quantity(ui, "thermals", "m/s", 0.0..=10.0, 0.1, 0.0, &mut wind.thermals.strength);
+			set_quantity(ui, "ridge lift", "", 0.0..=2.0, 0.01, RidgeLift::default().strength, &mut wind.ridge_lift.strength);
+			ui.horizontal(|ui| {
+				ui.label("seed");
+				ui.add(egui::DragValue::new(&mut wind.thermals.seed));
+				set_quantity(ui, "cloud base", "m", 100.0..=5000.0, 10.0, Thermals::default().cloud_base, &mut wind.thermals.cloud_base);
+			});
+			ui.horizontal(|ui| {
+				if ui.button("gust").on_hover_text("10 m/s headwind gust").clicked() {
+					wind.gusts.push(Gust {
+						start_time: t,
+						duration: 2.0,
+						amplitude: vec2(-10.0, 0.0),
+					});
+				}
+				if ui.button("microburst").on_hover_text("microburst 500 m ahead").clicked() {
+					wind.microbursts.push(Microburst {
+						x: plane.position().x() + 500.0,
+						radius: 300.0,
+						downdraft: 10.0,
+						outflow_height: 300.0,
+					});
+				}
+				if ui.button("hill").on_hover_text("50 m hill 300 m ahead, with ridge lift on its windward side (needs wind)").clicked() {
+					self.gs.tilemap.add_hill(plane.position().x() + 300.0, 100.0, 50.0);
+				}
+				if ui.button("❌ calm").clicked() {
+					*wind = WindField::default();
+				}
+			});
+			ui.label(format!("wind here: {:+.1} {:+.1} m/s", plane.wind.x(), plane.wind.y()));
+
 			ui.heading("camera");
 			ui.checkbox(&mut self.gs.camera_follows, "follows aircraft");
 			set_quantity(ui, "zoom", "", 0.5..=64.0, 32.0, 0.5, &mut self.gs.camera.zoom);
 			set_quantity(ui, "follow speed", "", 0.01..=1.0, 0.01, 0.3, &mut self.gs.camera_follow_speed);
 			ui.checkbox(&mut plane.draw_forces, "draw forces");
-			set_quantity(ui, "timewarp", "s/s", 1..=100, 1.0, 1, &mut self.gs.debug.timepassage);
+			set_quantity(ui, "timewarp", "s/s", 0.05..=100.0, 0.05, 1.0, &mut self.gs.debug.timepassage);
+			self.gs.debug.integrator.inspect_mut("integrator", ui);
 		});
 	}
 
@@ -172,15 +705,71 @@ impl App {
 			ui.columns(4, |cols| {
 				let plane = &self.gs.plane;
 				let ui = &mut cols[0];
-				ui.heading(&format!("airspeed: {:.0} m/s", plane.body.velocity.len()));
+				if let Some(modes) = self.gs.autopilot.annunciation() {
+					ui.colored_label(egui::Color32::DARK_GREEN, egui::RichText::new(modes).heading());
+				}
+				if let Some(phase) = self.gs.autoland.annunciation() {
+					ui.colored_label(egui::Color32::DARK_GREEN, egui::RichText::new(phase).heading());
+				} else if self.gs.autopilot.pitch_mode == PitchMode::Path {
+					if let Some(status) = self.gs.flight_plan.status(plane.position().x()) {
+						ui.colored_label(egui::Color32::DARK_GREEN, status);
+					}
+				}
+				if self.gs.autopilot.disconnect_warning > 0.0 {
+					ui.colored_label(egui::Color32::RED, egui::RichText::new("AP DISCONNECT").heading());
+				}
+				ui.heading(format!("IAS: {:.0} m/s", plane.indicated_airspeed()));
+				ui.heading(format!("TAS: {:.0} m/s", plane.true_airspeed()));
 				ui.heading(&format!("pitch: {:+.1}°", plane.body.rotation / DEG));
 				ui.heading(&format!("climb: {:+.1} m/s", plane.body.velocity.y()));
+				let vario = self.gs.variometer.reading;
+				let vario_color = if vario > 0.0 { egui::Color32::DARK_GREEN } else { ui.visuals().text_color() };
+				ui.colored_label(vario_color, egui::RichText::new(format!("vario: {vario:+.1} m/s")).heading());
 				ui.heading(&format!("altitude: {:.0} m", plane.body.position.y()));
-				ui.heading(&format!("AOA: {:+03.1}°", plane.winglet_aoa(&plane.wings) / DEG));
+				ui.label(format!("height above ground: {:.1} m", self.gs.height_above_ground()));
+				ui.heading(&format!("AOA: {:+03.1}°", plane.wings_aoa() / DEG));
+				if plane.main_wing().is_some_and(|w| plane.winglet_is_stalled(w)) {
+					ui.colored_label(egui::Color32::RED, "STALL");
+				}
+				if plane.contacts.borrow().iter().any(|c| c.part == HullPart::Wheel) {
+					ui.label(format!("ground speed: {:.1} m/s", plane.body.velocity.x()));
+					if plane.controls.brake_input() > 0.0 {
+						ui.label("BRAKES");
+					}
+				}
+				if plane.contacts.borrow().iter().any(|c| c.skidding) {
+					ui.colored_label(egui::Color32::RED, "SKID");
+				}
+				if plane.gear.is_retractable() {
+					ui.label(format!("gear: {}", plane.gear.status()));
+				}
+				if self.gs.gear_warning() {
+					ui.colored_label(egui::Color32::RED, "GEAR NOT DOWN");
+				}
+				if plane.gear.is_overspeed(plane.indicated_airspeed()) {
+					ui.colored_label(egui::Color32::RED, "GEAR OVERSPEED");
+				}
 
-				ui.label(&format!("elevator AOA: {:+02.1}°", plane.winglet_aoa(&plane.elevator) / DEG));
-				ui.label(&format!("elevator lift: {:.1} N", plane.winglet_lift(&plane.elevator).len()));
-				ui.label(&format!("elevator drag: {:.1} N", plane.winglet_induced_drag(&plane.elevator).len()));
+				if let Some(event) = self.gs.events.last() {
+					event_label(ui, event);
+				}
+
+				let engine = &plane.engine;
+				ui.label(format!("{:.0} RPM | {:.0} kW | {:.0} N", engine.rpm, engine.power(&plane.air) / 1e3, plane.propeller_thrust()));
+				ui.label(format!("fuel: {:.1} kg ({:.1} kg/h)", engine.fuel, engine.fuel_flow(&plane.air)));
+				if !engine.is_running() {
+					ui.colored_label(egui::Color32::RED, "ENGINE OUT");
+				}
+
+				for surface in &plane.surfaces {
+					ui.label(format!(
+						"{}: AOA {:+02.1}° | lift {:.1} N | drag {:.1} N",
+						surface.name,
+						plane.winglet_aoa(surface) / DEG,
+						plane.winglet_lift(surface).len(),
+						plane.winglet_induced_drag(surface).len()
+					));
+				}
 
 				self.plot(&mut cols[1], 0);
 				self.plot(&mut cols[2], 1);
@@ -192,20 +781,26 @@ impl App {
 
 	fn plot(&mut self, ui: &mut egui::Ui, plot_i: usize) {
 		use egui_plot::{Line, PlotPoints};
+		let (plotter, axes) = match &self.performance {
+			Some((_, plotter)) if self.plot_performance[plot_i] => (plotter, &mut self.performance_axes[plot_i]),
+			_ => (&self.gs.plotter, &mut self.plot_axes[plot_i]),
+		};
+		let mut clear = false;
 		ui.horizontal(|ui| {
-			for (sel, id) in iter::zip(self.plot_axes[plot_i].iter_mut(), [(plot_i, 0), (plot_i, 1)]) {
-				egui::ComboBox::from_id_source(id).selected_text(&self.gs.plotter.labels[*sel]).show_ui(ui, |ui| {
-					for (i, label) in self.gs.plotter.labels.iter().enumerate() {
+			for (sel, id) in iter::zip(axes.iter_mut(), [(plot_i, 0), (plot_i, 1)]) {
+				egui::ComboBox::from_id_source(id).selected_text(&plotter.labels[*sel]).show_ui(ui, |ui| {
+					for (i, label) in plotter.labels.iter().enumerate() {
 						ui.selectable_value(sel, i, label);
 					}
 				});
 			}
-			if ui.button("❌").clicked() {
-				self.gs.plotter.clear()
+			if self.performance.is_some() {
+				ui.toggle_value(&mut self.plot_performance[plot_i], "🚀").on_hover_text("show performance curves");
 			}
+			clear = ui.button("❌").clicked();
 		});
 
-		let line = self.gs.plotter.line(self.plot_axes[plot_i][0], self.plot_axes[plot_i][1]);
+		let line = plotter.line(axes[0], axes[1]);
 		egui_plot::Plot::new("example_plot")
 			.show_axes(true)
 			.allow_drag(true)
@@ -214,11 +809,14 @@ impl App {
 			.center_x_axis(false)
 			.center_x_axis(false)
 			.height(300.0)
-			.x_axis_label(&self.gs.plotter.labels[self.plot_axes[plot_i][0]])
-			.y_axis_label(&self.gs.plotter.labels[self.plot_axes[plot_i][1]])
+			.x_axis_label(&plotter.labels[axes[0]])
+			.y_axis_label(&plotter.labels[axes[1]])
 			.show(ui, |plot_ui| {
 				plot_ui.line(line);
 			});
+		if clear {
+			self.gs.plotter.clear()
+		}
 	}
 
 	fn left_panel(&mut self, ctx: &egui::Context) {
@@ -226,7 +824,7 @@ impl App {
 		if self.ui_state.gamestate_open {
 			egui::SidePanel::left("left_panel").show(ctx, |ui| {
 				if ui.button("❌ reset").clicked() {
-					self.gs = default();
+					self.gs.reset();
 				}
 				self.gs.inspect_mut("gamestate", ui);
 			});
@@ -266,6 +864,9 @@ impl App {
 		});
 
 		let rect = response.inner.rect;
+		if !context_menu_open {
+			self.ui_state.context_menu_pos = None;
+		}
 		// Hack to avoid Canvas clicks when context menu is open.
 		// `ctx.is_using_pointer()`, `ctx.wants_pointer_input()` don't have the desired behavior.
 		if let Some(mouse_pos) = ctx.input(|inputs| inputs.pointer.interact_pos()) {
@@ -276,7 +877,37 @@ impl App {
 	}
 
 	// Context menu (right-click) in Main Canvas.
-	fn context_menu(&mut self, ctx: &egui::Context, ui: &mut Ui) {}
+	fn context_menu(&mut self, ctx: &egui::Context, ui: &mut Ui) {
+		let pos = *self.ui_state.context_menu_pos.get_or_insert(self.gs.mouse_pos);
+		let plan = &mut self.gs.flight_plan;
+		ui.label(format!("x {:.0} m | altitude {:.0} m", pos.x(), pos.y()));
+		if ui.button("📍 add waypoint").clicked() {
+			let airspeed = plan.waypoints.last().map_or(self.gs.plane.indicated_airspeed().round(), |w| w.airspeed);
+			plan.add(Waypoint::new(pos, airspeed));
+			ui.close_menu();
+		}
+		if let Some(i) = plan.nearest(pos, 10.0 / self.gs.camera.zoom) {
+			if ui.button(format!("❌ remove waypoint {}", i + 1)).clicked() {
+				plan.waypoints.remove(i);
+				ui.close_menu();
+			}
+		}
+		if ui.button("🛬 land here").clicked() {
+			match self.gs.engage_autoland(pos.x()) {
+				Ok(()) => self.ui_state.autoland_error.clear(),
+				Err(e) => self.ui_state.autoland_error = format!("{e:#}"),
+			}
+			ui.close_menu();
+		}
+		let plan = &mut self.gs.flight_plan;
+		if !plan.waypoints.is_empty() && ui.button("▶ fly flight plan").clicked() {
+			match plan.engage(&mut self.gs.autopilot, &self.gs.plane) {
+				Ok(()) => self.ui_state.flight_plan_error.clear(),
+				Err(e) => self.ui_state.flight_plan_error = format!("{e:#}"),
+			}
+			ui.close_menu();
+		}
+	}
 
 	fn scenegraph_window(&mut self, ctx: &egui::Context) {
 		egui::Window::new("🎬 scenegraph") //.
@@ -292,7 +923,7 @@ impl App {
 			.open(&mut self.ui_state.gamestate_open)
 			.show(ctx, |ui| {
 				if ui.button("❌ reset").clicked() {
-					self.gs = default();
+					self.gs.reset();
 				}
 				inspect(ui, "state", &mut self.gs);
 			});
@@ -317,6 +948,9 @@ impl eframe::App for App {
 	// Called by the frame work to save state before shutdown.
 	fn save(&mut self, storage: &mut dyn eframe::Storage) {
 		log::info!("persisting egui state");
+		if let Err(e) = Settings::save_controls(&self.gs.controls) {
+			log::error!("{}: {e:#}", Settings::FILE);
+		}
 
 		// serialize as base64(gzip(rmp))
 		let mut s = base64::write::EncoderStringWriter::new(&BASE64_STANDARD);
@@ -365,6 +999,11 @@ fn with_labels<X>(ui: &mut egui::Ui, prefix: &str, suffix: &str, add_contents: i
 	});
 }
 
+fn event_label(ui: &mut egui::Ui, event: &GameEvent) {
+	let color = if event.is_alert() { egui::Color32::RED } else { ui.visuals().text_color() };
+	ui.colored_label(color, event.message());
+}
+
 fn set_quantity<T>(ui: &mut egui::Ui, prefix: &str, suffix: &str, range: std::ops::RangeInclusive<T>, speed: f64, default: T, value: &mut T)
 where
 	T: egui::emath::Numeric + PartialEq + Copy,
@@ -379,6 +1018,10 @@ where
 	});
 }
 
+fn default_performance_axes() -> [[usize; 2]; NUM_PLOTS] {
+	[[0, 3], [0, 1], [0, 4]] // climb rate, thrust required and glide ratio vs. airspeed
+}
+
 fn set_angle(ui: &mut egui::Ui, prefix: &str, range: std::ops::RangeInclusive<f32>, default: f32, value: &mut f32) {
 	ui.horizontal(|ui| {
 		ui.label(prefix);
@@ -390,6 +1033,18 @@ fn set_angle(ui: &mut egui::Ui, prefix: &str, range: std::ops::RangeInclusive<f3
 	});
 }
 
+fn set_lift_curve(ui: &mut egui::Ui, prefix: &str, max_drag: f32, default: &LiftCurve, value: &mut LiftCurve) {
+	set_quantity(ui, &format!("{prefix} drag"), "N/√(m/s)", 0.0..=max_drag, 0.001, default.drag_factor, &mut value.drag_factor);
+	set_quantity(ui, &format!("{prefix} l2d"), "", 0.0..=20.0, 0.1, default.lift_to_drag, &mut value.lift_to_drag);
+	set_angle(ui, &format!("{prefix} critical aoa"), 0.0..=(90.0 * DEG), default.critical_aoa, &mut value.critical_aoa);
+	set_quantity(ui, &format!("{prefix} post-stall lift"), "", 0.0..=1.0, 0.01, default.post_stall_lift, &mut value.post_stall_lift);
+	let max_sep = 4.0 * max_drag;
+	set_quantity(ui, &format!("{prefix} sep. drag"), "N/√(m/s)", 0.0..=max_sep, 0.01, default.separated_drag, &mut value.separated_drag);
+	if ui.button(format!("{prefix}: no stall")).on_hover_text("simple lift/drag model, without stall").clicked() {
+		*value = LiftCurve::simple(value.drag_factor, value.lift_to_drag);
+	}
+}
+
 fn set_vec2(ui: &mut egui::Ui, prefix: &str, suffix: &str, range: std::ops::RangeInclusive<f64>, speed: f64, default: vec2f, value: &mut vec2f) {
 	ui.horizontal(|ui| {
 		ui.label(prefix);
@@ -438,6 +1093,15 @@ fn inspect_vec2(ui: &mut egui::Ui, prefix: &str, suffix: &str, decimals: usize,
 	inspect_values2(ui, prefix, suffix, decimals, x, y);
 }
 
+/// Preset named `name` (or the default aircraft), loaded again only when the name changes.
+fn preset_default<'a>(cache: &'a mut Option<(String, Plane)>, name: &str) -> &'a Plane {
+	if cache.as_ref().map(|(n, _)| n.as_str()) != Some(name) {
+		let preset = AircraftPreset::find(name).and_then(|p| p.load().ok()).unwrap_or_else(Plane::default);
+		*cache = Some((name.to_string(), preset));
+	}
+	&cache.as_ref().expect("just loaded").1
+}
+
 fn slider<T>(ui: &mut egui::Ui, prefix: &str, suffix: &str, range: std::ops::RangeInclusive<T>, value: &mut T)
 where
 	T: egui::emath::Numeric + PartialEq + Copy,
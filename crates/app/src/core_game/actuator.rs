@@ -0,0 +1,92 @@
+use crate::prelude::*;
+
+/// Servo or linkage moving a control surface to the commanded deflection.
+///
+/// The defaults are ideal: the surface follows the command instantly, within ±90°.
+/// Real actuators have limited travel and speed, lag behind the command,
+/// and can't hold full deflection against the air load at high speed (blowback).
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect, PartialEq)]
+#[serde(default)]
+pub struct Actuator {
+	/// Lowest and highest deflection (rad). Commands beyond are clipped.
+	pub limits: vec2f,
+	/// Fastest deflection change (rad/s). 0: unlimited.
+	pub max_rate: f32,
+	/// Time constant of the first-order lag behind the command (s). 0: none.
+	pub time_constant: f32,
+	/// Indicated airspeed (m/s) up to which the actuator can hold full deflection. 0: any.
+	/// Above, the hinge moment grows with dynamic pressure and the reachable deflection shrinks with 1/airspeed².
+	pub blowback_speed: f32,
+}
+
+impl Actuator {
+	/// Deflection the actuator can reach for `command` (rad) at `indicated_airspeed` (m/s).
+	pub fn reachable(&self, command: f32, indicated_airspeed: f32) -> f32 {
+		let scale = match self.blowback_speed {
+			v if v > 0.0 && indicated_airspeed > v => (v / indicated_airspeed).powi(2),
+			_ => 1.0,
+		};
+		command.clamp(self.limits.x() * scale, self.limits.y() * scale)
+	}
+
+	/// Deflection after moving from `deflection` towards `command` for `dt` seconds.
+	pub fn tick(&self, dt: f32, command: f32, indicated_airspeed: f32, deflection: f32) -> f32 {
+		let target = self.reachable(command, indicated_airspeed);
+		let mut step = target - deflection;
+		if self.time_constant > 0.0 {
+			step *= 1.0 - (-dt / self.time_constant).exp();
+		}
+		if self.max_rate > 0.0 {
+			let max = self.max_rate * dt;
+			step = step.clamp(-max, max);
+		}
+		deflection + step
+	}
+
+	pub fn is_ideal(&self) -> bool {
+		*self == Self::default()
+	}
+}
+
+impl Default for Actuator {
+	fn default() -> Self {
+		Self {
+			limits: vec2(-90.0 * DEG, 90.0 * DEG),
+			max_rate: 0.0,
+			time_constant: 0.0,
+			blowback_speed: 0.0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn limits_rate_and_lag() {
+		let ideal = Actuator::default();
+		assert_eq!(ideal.tick(0.001, 0.3, 100.0, 0.0), 0.3);
+
+		let servo = Actuator {
+			limits: vec2(-0.4, 0.2),
+			max_rate: 1.0,
+			time_constant: 0.1,
+			blowback_speed: 50.0,
+		};
+		assert_eq!(servo.reachable(0.3, 40.0), 0.2);
+		assert_eq!(servo.reachable(-0.3, 100.0), -0.1);
+
+		// rate limited at first, then lagging: ~63% of the remaining way per time constant
+		let dt = 0.001;
+		let mut deflection = 0.0;
+		for _ in 0..100 {
+			deflection = servo.tick(dt, -0.4, 40.0, deflection);
+		}
+		assert!((deflection + 0.1).abs() < 1e-4, "{deflection}");
+		for _ in 0..1000 {
+			deflection = servo.tick(dt, -0.4, 40.0, deflection);
+		}
+		assert!(deflection > -0.4 && deflection < -0.39, "{deflection}");
+	}
+}
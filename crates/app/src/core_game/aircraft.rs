@@ -0,0 +1,254 @@
+use crate::prelude::*;
+
+/// Aircraft design as stored in a human-readable TOML file (see `aircraft/*.toml`).
+///
+/// Only the design is stored: mass stations, surfaces, gear, engine and sprite.
+/// Flight state (position, velocity, control inputs, ...) is not part of the file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AircraftFile {
+	pub name: String,
+	#[serde(default)]
+	pub description: String,
+	pub sprite: Sprite,
+	/// Drawn size of the fuselage sprite (m).
+	pub sprite_scale: vec2f,
+
+	pub stations: Vec<MassStation>,
+	/// Allowed center of gravity range, x relative to the datum (m).
+	pub cg_forward_limit: f32,
+	pub cg_aft_limit: f32,
+
+	pub surfaces: Vec<Winglet>,
+	pub body_drag: f32,
+	pub wheels: [vec2f; 2],
+	#[serde(default)]
+	pub tyre: Tyre,
+	#[serde(default)]
+	pub gear: LandingGear,
+	pub hull: Vec<HullPoint>,
+
+	pub engine: Powerplant,
+	pub propeller_pos: vec2f,
+}
+
+/// An aircraft file bundled with the game.
+pub struct AircraftPreset {
+	pub name: &'static str,
+	pub toml: &'static str,
+}
+
+pub const AIRCRAFT_PRESETS: [AircraftPreset; 4] = [
+	AircraftPreset {
+		name: "trainer",
+		toml: include_str!("../../aircraft/trainer.toml"),
+	},
+	AircraftPreset {
+		name: "glider",
+		toml: include_str!("../../aircraft/glider.toml"),
+	},
+	AircraftPreset {
+		name: "aerobatic",
+		toml: include_str!("../../aircraft/aerobatic.toml"),
+	},
+	AircraftPreset {
+		name: "heavy cargo",
+		toml: include_str!("../../aircraft/heavy_cargo.toml"),
+	},
+];
+
+impl AircraftPreset {
+	pub fn find(name: &str) -> Option<&'static Self> {
+		AIRCRAFT_PRESETS.iter().find(|p| p.name == name)
+	}
+
+	pub fn load(&self) -> Result<Plane> {
+		Ok(AircraftFile::parse(self.toml).with_context(|| format!("preset '{}'", self.name))?.to_plane())
+	}
+}
+
+impl AircraftFile {
+	/// Parse and validate an aircraft file.
+	pub fn parse(toml: &str) -> Result<Self> {
+		let file: Self = toml::from_str(toml).context("invalid aircraft file")?;
+		file.validate()?;
+		Ok(file)
+	}
+
+	pub fn to_toml(&self) -> Result<String> {
+		let mut value = toml::Value::try_from(self)?;
+		shorten_floats(&mut value);
+		Ok(toml::to_string(&value)?)
+	}
+
+	/// The design of `plane`, without its flight state.
+	pub fn from_plane(plane: &Plane) -> Self {
+		let mut engine = plane.engine.clone();
+		engine.throttle = 0.0;
+		engine.rpm = 0.0;
+		engine.failed = false;
+		let mut surfaces = plane.surfaces.clone();
+		surfaces.iter_mut().for_each(|s| s.deflection = 0.0);
+		Self {
+			name: plane.name.clone(),
+			description: plane.description.clone(),
+			sprite: plane.sprite,
+			sprite_scale: plane.sprite_scale,
+			stations: plane.stations.clone(),
+			cg_forward_limit: plane.cg_forward_limit,
+			cg_aft_limit: plane.cg_aft_limit,
+			surfaces,
+			body_drag: plane.body_drag,
+			wheels: plane.wheels,
+			tyre: plane.tyre.clone(),
+			gear: LandingGear {
+				down_selected: true,
+				extension: 1.0,
+				..plane.gear.clone()
+			},
+			hull: plane.hull.clone(),
+			engine,
+			propeller_pos: plane.propeller_pos,
+		}
+	}
+
+	/// A new aircraft with this design, parked at the start position.
+	pub fn to_plane(&self) -> Plane {
+		let mut plane = Plane {
+			name: self.name.clone(),
+			description: self.description.clone(),
+			sprite: self.sprite,
+			sprite_scale: self.sprite_scale,
+			stations: self.stations.clone(),
+			cg_forward_limit: self.cg_forward_limit,
+			cg_aft_limit: self.cg_aft_limit,
+			surfaces: self.surfaces.clone(),
+			body_drag: self.body_drag,
+			wheels: self.wheels,
+			tyre: self.tyre.clone(),
+			gear: self.gear.clone(),
+			hull: self.hull.clone(),
+			engine: self.engine.clone(),
+			propeller_pos: self.propeller_pos,
+			..Plane::default()
+		};
+		plane.update_mass_properties();
+		plane
+	}
+
+	/// Check the design for values that would break the simulation.
+	/// All problems are reported at once, one per line.
+	pub fn validate(&self) -> Result<()> {
+		let mut errors = vec![];
+		let mut check = |ok: bool, msg: String| {
+			if !ok {
+				errors.push(msg)
+			}
+		};
+
+		check(!self.name.trim().is_empty(), "name is empty".into());
+		check(self.sprite_scale.iter().all(|v| v > 0.0), format!("sprite_scale must be positive, got {:?}", self.sprite_scale));
+
+		check(!self.stations.is_empty(), "no mass stations".into());
+		for station in &self.stations {
+			check(station.mass >= 0.0, format!("station '{}': negative mass {} kg", station.name, station.mass));
+			check(station.gyration_radius >= 0.0, format!("station '{}': negative gyration_radius", station.name));
+		}
+		check(self.stations.iter().map(|s| s.mass).sum::<f32>() > 0.0, "total mass must be positive".into());
+		check(
+			self.cg_forward_limit > self.cg_aft_limit,
+			format!("cg_forward_limit ({}) must be ahead of cg_aft_limit ({})", self.cg_forward_limit, self.cg_aft_limit),
+		);
+
+		check(self.surfaces.iter().any(|s| s.kind == SurfaceKind::MainWing), "no surface of kind MainWing".into());
+		for (i, surface) in self.surfaces.iter().enumerate() {
+			let name = &surface.name;
+			check(!name.trim().is_empty(), format!("surface #{}: name is empty", i + 1));
+			check(self.surfaces[..i].iter().all(|s| &s.name != name), format!("surface '{name}': duplicate name"));
+			let curve = &surface.curve;
+			check(curve.drag_factor >= 0.0, format!("surface '{name}': negative drag_factor"));
+			check(curve.lift_to_drag >= 0.0, format!("surface '{name}': negative lift_to_drag"));
+			check(
+				curve.critical_aoa > 0.0 && curve.critical_aoa <= 90.0 * DEG,
+				format!("surface '{name}': critical_aoa must be between 0 and 90° (in radians), got {}", curve.critical_aoa),
+			);
+			check((0.0..=1.0).contains(&curve.post_stall_lift), format!("surface '{name}': post_stall_lift must be 0..1"));
+			check(surface.max_deflection.abs() <= 90.0 * DEG, format!("surface '{name}': max_deflection beyond 90°"));
+			let actuator = &surface.actuator;
+			check(actuator.limits.x() <= actuator.limits.y(), format!("surface '{name}': actuator limits must be [lowest, highest]"));
+			check(
+				actuator.max_rate >= 0.0 && actuator.time_constant >= 0.0 && actuator.blowback_speed >= 0.0,
+				format!("surface '{name}': negative actuator max_rate, time_constant or blowback_speed"),
+			);
+			check(surface.size.iter().all(|v| v > 0.0), format!("surface '{name}': size must be positive"));
+		}
+		check(self.body_drag >= 0.0, "negative body_drag".into());
+		check(self.tyre.max_brake_force >= 0.0, "tyre: negative max_brake_force".into());
+		check(self.gear.transition_time > 0.0, "gear: transition_time must be positive".into());
+		check(self.gear.drag >= 0.0, "gear: negative drag".into());
+		check(self.gear.extension == 1.0 && self.gear.down_selected, "gear: must start down".into());
+
+		let engine = &self.engine;
+		check(engine.max_rpm > engine.idle_rpm && engine.idle_rpm >= 0.0, "engine: need 0 <= idle_rpm < max_rpm".into());
+		check(engine.spool_time > 0.0, "engine: spool_time must be positive".into());
+		check(engine.max_power >= 0.0 && engine.static_thrust >= 0.0, "engine: negative power or thrust".into());
+		check((0.0..=1.0).contains(&engine.prop_efficiency), "engine: prop_efficiency must be 0..1".into());
+		check(engine.prop_max_speed > 0.0, "engine: prop_max_speed must be positive".into());
+		check(engine.fuel_consumption >= 0.0, "engine: negative fuel_consumption".into());
+		let capacity: f32 = self.stations.iter().filter(|s| s.kind == StationKind::FuelTank).map(|s| s.mass).sum();
+		check(
+			engine.fuel >= 0.0 && engine.fuel as f32 <= capacity + 1e-3,
+			format!("engine: fuel ({} kg) exceeds the FuelTank capacity ({capacity} kg)", engine.fuel),
+		);
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			bail!("invalid aircraft '{}':\n{}", self.name, errors.join("\n"))
+		}
+	}
+}
+
+/// Write f32 values as `0.6` rather than `0.6000000238418579`.
+fn shorten_floats(value: &mut toml::Value) {
+	match value {
+		toml::Value::Float(f) => *f = (*f as f32).to_string().parse().unwrap_or(*f),
+		toml::Value::Array(array) => array.iter_mut().for_each(shorten_floats),
+		toml::Value::Table(table) => table.iter_mut().for_each(|(_, v)| shorten_floats(v)),
+		_ => (),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn presets_are_valid() {
+		for preset in &AIRCRAFT_PRESETS {
+			let plane = preset.load().unwrap_or_else(|e| panic!("{e:#}"));
+			let file = AircraftFile::from_plane(&plane);
+			assert!(plane.cg_in_limits(), "{}: CG out of limits", preset.name);
+			let stability = plane.static_stability().unwrap();
+			assert_eq!(stability.verdict(), StabilityVerdict::Stable, "{}: {stability:?}", preset.name);
+			// round trip
+			assert_eq!(AircraftFile::parse(&file.to_toml().unwrap()).unwrap().to_toml().unwrap(), file.to_toml().unwrap());
+		}
+	}
+
+	#[test]
+	fn trainer_is_default() {
+		let trainer = AircraftFile::parse(AIRCRAFT_PRESETS[0].toml).unwrap();
+		assert_eq!(trainer.to_toml().unwrap(), AircraftFile::from_plane(&Plane::default()).to_toml().unwrap());
+	}
+
+	#[test]
+	fn reports_errors() {
+		let mut file = AircraftFile::from_plane(&Plane::default());
+		file.surfaces.retain(|s| s.kind != SurfaceKind::MainWing);
+		file.cg_aft_limit = 1.0;
+		let err = format!("{:#}", AircraftFile::parse(&file.to_toml().unwrap()).unwrap_err());
+		assert!(err.contains("MainWing"), "{err}");
+		assert!(err.contains("cg_forward_limit"), "{err}");
+	}
+}
@@ -0,0 +1,134 @@
+use crate::prelude::*;
+
+/// International Standard Atmosphere (troposphere + lower stratosphere),
+/// optionally hotter or colder than standard by `temperature_offset`.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Atmosphere {
+	/// Temperature difference with respect to ISA (K). Positive: hot day.
+	pub temperature_offset: f32,
+	/// Pressure at altitude 0 (Pa).
+	pub sea_level_pressure: f32,
+}
+
+/// Local state of the air at some altitude.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect)]
+pub struct AirData {
+	/// K
+	pub temperature: f32,
+	/// Pa
+	pub pressure: f32,
+	/// kg/m³
+	pub density: f32,
+}
+
+impl Atmosphere {
+	pub const SEA_LEVEL_TEMPERATURE: f32 = 288.15; // K
+	pub const SEA_LEVEL_PRESSURE: f32 = 101325.0; // Pa
+	pub const SEA_LEVEL_DENSITY: f32 = 1.225; // kg/m³
+
+	const LAPSE_RATE: f32 = 0.0065; // K/m
+	const TROPOPAUSE: f32 = 11000.0; // m
+	const GAS_CONSTANT: f32 = 287.05; // J/(kg K), dry air
+	const GRAVITY: f32 = 9.80665; // m/s²
+
+	/// Air temperature, pressure and density at `altitude` (m).
+	pub fn at(&self, altitude: f32) -> AirData {
+		let h = altitude.clamp(0.0, 20000.0); // ISA below sea level / above 20 km not modelled
+		let (t0, p0, r, g, l) = (Self::SEA_LEVEL_TEMPERATURE, self.sea_level_pressure, Self::GAS_CONSTANT, Self::GRAVITY, Self::LAPSE_RATE);
+
+		let t11 = t0 - l * Self::TROPOPAUSE;
+		let p11 = p0 * (t11 / t0).powf(g / (r * l));
+
+		// pressure follows the standard profile (pressure altitude == geometric altitude),
+		// temperature offset only changes density.
+		let (isa_temperature, pressure) = if h < Self::TROPOPAUSE {
+			let t = t0 - l * h;
+			(t, p0 * (t / t0).powf(g / (r * l)))
+		} else {
+			(t11, p11 * f32::exp(-g * (h - Self::TROPOPAUSE) / (r * t11)))
+		};
+
+		let temperature = isa_temperature + self.temperature_offset;
+		let density = pressure / (r * temperature);
+
+		AirData { temperature, pressure, density }
+	}
+
+	/// Altitude (m) in the standard atmosphere where density equals `density`.
+	pub fn density_altitude(density: f32) -> f32 {
+		// bisection: density is monotonic in altitude
+		let isa = Atmosphere::default();
+		let (mut lo, mut hi) = (0.0, 20000.0);
+		for _ in 0..32 {
+			let mid = 0.5 * (lo + hi);
+			if isa.at(mid).density > density {
+				lo = mid;
+			} else {
+				hi = mid;
+			}
+		}
+		0.5 * (lo + hi)
+	}
+}
+
+impl AirData {
+	/// Density relative to standard sea level.
+	/// Aerodynamic coefficients are defined at sea level and scale with this ratio.
+	pub fn density_ratio(&self) -> f32 {
+		self.density / Atmosphere::SEA_LEVEL_DENSITY
+	}
+
+	/// Indicated airspeed (assuming no instrument errors, incompressible flow) for given true airspeed.
+	pub fn indicated_airspeed(&self, true_airspeed: f32) -> f32 {
+		true_airspeed * self.density_ratio().sqrt()
+	}
+}
+
+impl Default for Atmosphere {
+	fn default() -> Self {
+		Self {
+			temperature_offset: 0.0,
+			sea_level_pressure: Self::SEA_LEVEL_PRESSURE,
+		}
+	}
+}
+
+impl Default for AirData {
+	fn default() -> Self {
+		Atmosphere::default().at(0.0)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn standard_atmosphere() {
+		let isa = Atmosphere::default();
+		let sl = isa.at(0.0);
+		assert!((sl.density - Atmosphere::SEA_LEVEL_DENSITY).abs() < 0.001);
+
+		// reference values from ISA tables
+		let air = isa.at(5000.0);
+		assert!((air.temperature - 255.65).abs() < 0.01);
+		assert!((air.pressure - 54020.0).abs() < 50.0);
+		assert!((air.density - 0.7361).abs() < 0.001);
+
+		let air = isa.at(15000.0);
+		assert!((air.temperature - 216.65).abs() < 0.01);
+		assert!((air.density - 0.1948).abs() < 0.002);
+	}
+
+	#[test]
+	fn hot_day_has_high_density_altitude() {
+		let hot = Atmosphere {
+			temperature_offset: 30.0,
+			..default()
+		};
+		let density_altitude = Atmosphere::density_altitude(hot.at(0.0).density);
+		assert!(density_altitude > 1000.0 && density_altitude < 1200.0);
+		assert!((Atmosphere::density_altitude(Atmosphere::default().at(3000.0).density) - 3000.0).abs() < 1.0);
+	}
+}
@@ -0,0 +1,250 @@
+use crate::prelude::*;
+
+/// Automatic landing: flies the approach, flare and rollout to a touchdown point on the runway.
+///
+/// Works the same controls as the pilot: pitch and throttle through the `Autopilot`, brakes and gear directly.
+/// So autolands show up in the event log just like the pilot's own landings.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Autoland {
+	pub phase: AutolandPhase,
+	/// Aim point on the runway surface: x and altitude (m).
+	pub touchdown: vec2f,
+	/// Glide path angle (rad).
+	pub glide_slope: f32,
+	/// Indicated airspeed on the approach (m/s).
+	pub approach_speed: f32,
+	/// Wheel height above the terrain where the flare starts (m).
+	pub flare_height: f32,
+	/// Sink rate aimed for at touchdown (m/s).
+	pub touchdown_sink_rate: f32,
+	/// Sink rate error (m/s) → target pitch (rad) during the flare.
+	/// Tighter than the autopilot's vertical speed hold: the flare has only a few seconds to round out.
+	pub flare_pid: Pid,
+	/// Wheel altitude held until the glide path is captured from below (m).
+	#[inspect(hide)]
+	pub capture_altitude: f32,
+	/// Where the wheels touched down, ahead of the aim point (m).
+	pub touchdown_error: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AutolandPhase {
+	#[default]
+	Off,
+	/// Capturing and tracking the glide path, auto-throttle at approach speed.
+	Approach,
+	/// Throttle idle, sink rate reduced with height by pitching up.
+	Flare,
+	/// On the wheels: nose held in the ground attitude, braking to a stop.
+	Rollout,
+}
+
+impl Autoland {
+	/// Start an approach to `touchdown` (a point on the runway surface), lowering the gear.
+	pub fn engage(&mut self, touchdown: vec2f, plane: &mut Plane, autopilot: &mut Autopilot) -> Result<()> {
+		if touchdown.x() <= plane.position().x() {
+			bail!("touchdown point is behind the aircraft");
+		}
+		if plane.weight_on_wheels() {
+			bail!("on the ground");
+		}
+		self.touchdown = touchdown;
+		self.capture_altitude = plane.wheel_altitude();
+		self.touchdown_error = None;
+		self.phase = AutolandPhase::Approach;
+		plane.gear.down_selected = true;
+		autopilot.engage(PitchMode::Path, plane);
+		autopilot.engage_autothrottle(plane);
+		Ok(())
+	}
+
+	pub fn is_engaged(&self) -> bool {
+		self.phase != AutolandPhase::Off
+	}
+
+	/// Glide path altitude of the wheels at `x` (m).
+	pub fn glide_path(&self, x: f32) -> f32 {
+		self.touchdown.y() + self.glide_slope.tan() * (self.touchdown.x() - x)
+	}
+
+	/// The pilot moved the pitch control and/or the throttle: hand those axes back (see `Autopilot::pilot_input`).
+	/// While landing, either input gives up the landing and hands back both.
+	pub fn pilot_input(&mut self, pitch: bool, throttle: bool, autopilot: &mut Autopilot) {
+		let takeover = self.is_engaged() && (pitch || throttle);
+		if takeover {
+			self.phase = AutolandPhase::Off;
+		}
+		autopilot.pilot_input(pitch || takeover, throttle || takeover);
+	}
+
+	/// Fly the current phase for `dt` seconds, move on to the next when due.
+	/// Gives up when the pilot disconnects the autopilot's pitch channel.
+	pub fn tick(&mut self, dt: f32, plane: &mut Plane, autopilot: &mut Autopilot) {
+		if !self.is_engaged() {
+			return;
+		}
+		if autopilot.pitch_mode == PitchMode::Off {
+			self.phase = AutolandPhase::Off;
+			return;
+		}
+
+		let height = plane.wheel_height();
+		let ground_speed = plane.body.velocity.x();
+		let approach_sink_rate = self.glide_slope.tan() * ground_speed;
+		match self.phase {
+			AutolandPhase::Off => (),
+			AutolandPhase::Approach => {
+				// follow the wheels' glide path with the center of mass
+				let wheel_depth = plane.altitude() - plane.wheel_altitude();
+				let path = self.glide_path(plane.position().x());
+				let captured = path < self.capture_altitude;
+				autopilot.target_altitude = wheel_depth + path.min(self.capture_altitude);
+				autopilot.target_vertical_speed = if captured { -approach_sink_rate } else { 0.0 };
+				autopilot.target_airspeed = self.approach_speed;
+				if height < self.flare_height {
+					self.phase = AutolandPhase::Flare;
+					autopilot.autothrottle = false;
+					autopilot.engage(PitchMode::Pitch, plane);
+					self.flare_pid.reset(plane.pitch());
+				}
+			}
+			AutolandPhase::Flare => {
+				plane.engine.throttle = 0.0;
+				let fraction = (height / self.flare_height).clamp(0.0, 1.0);
+				let sink_rate = self.touchdown_sink_rate + fraction * (approach_sink_rate - self.touchdown_sink_rate).max(0.0);
+				let max = autopilot.max_pitch;
+				autopilot.target_pitch = self.flare_pid.update(dt, -sink_rate - plane.body.velocity.y(), -max..=max);
+				if plane.weight_on_wheels() {
+					self.phase = AutolandPhase::Rollout;
+					self.touchdown_error = Some(plane.position().x() - self.touchdown.x());
+					autopilot.engage(PitchMode::Pitch, plane);
+					autopilot.target_pitch = plane.ground_attitude().min(0.0);
+				}
+			}
+			AutolandPhase::Rollout => {
+				plane.engine.throttle = 0.0;
+				plane.controls.brakes = 1.0;
+				if ground_speed.abs() < 0.5 {
+					self.phase = AutolandPhase::Off;
+					plane.controls.parking_brake = true;
+					plane.controls.pitch = 0.0;
+					autopilot.pitch_mode = PitchMode::Off;
+				}
+			}
+		}
+	}
+
+	/// Phase for the HUD.
+	pub fn annunciation(&self) -> Option<&'static str> {
+		match self.phase {
+			AutolandPhase::Off => None,
+			AutolandPhase::Approach => Some("LAND: APPROACH"),
+			AutolandPhase::Flare => Some("LAND: FLARE"),
+			AutolandPhase::Rollout => Some("LAND: ROLLOUT"),
+		}
+	}
+
+	/// Aim point and glide path, while engaged. `pixel`: size of a screen pixel in m.
+	pub fn draw(&self, sg: &mut Scenegraph, pixel: f32) {
+		if !self.is_engaged() {
+			return;
+		}
+		const Z: f32 = 1.5;
+		let vertex = |p: vec2f| TerrainVertex::new(p.append(Z)).with(|v| v.color = pack4xu8(MAGENTA));
+		let start = self.touchdown.x() - (self.capture_altitude - self.touchdown.y()).max(0.0) / self.glide_slope.tan().max(f32::EPSILON);
+		let (a, b) = (vec2(start, self.glide_path(start)), self.touchdown);
+		let normal = 1.5 * pixel * vec2(a.y() - b.y(), b.x() - a.x()).normalized();
+		sg.meshbuffer.push_rect(&[a - normal, b - normal, b + normal, a + normal].map(vertex));
+		let size = 8.0 * pixel;
+		sg.meshbuffer.push_triangle(&[vec2(-size, size), vec2(size, size), vec2(0.0, 0.0)].map(|d| vertex(b + d)));
+	}
+}
+
+impl Plane {
+	/// Altitude of the lowest wheel (m).
+	pub fn wheel_altitude(&self) -> f32 {
+		(0..2).map(|i| self.body.transform_rel_pos(self.gear.wheel_pos(i, self.wheels[i])).y()).fold(f32::INFINITY, f32::min)
+	}
+
+	/// Height of the lowest wheel above `ground_level` (m).
+	pub fn wheel_height(&self) -> f32 {
+		self.wheel_altitude() - self.ground_level
+	}
+
+	/// Pitch (rad) with both wheels on level ground.
+	pub fn ground_attitude(&self) -> f32 {
+		let [a, b] = self.wheels;
+		let d = if a.x() < b.x() { b - a } else { a - b };
+		-f32::atan2(d.y(), d.x())
+	}
+}
+
+impl Default for Autoland {
+	fn default() -> Self {
+		Self {
+			phase: AutolandPhase::Off,
+			touchdown: vec::ZERO,
+			glide_slope: 3.0 * DEG,
+			approach_speed: 60.0,
+			flare_height: 10.0,
+			touchdown_sink_rate: 0.5,
+			flare_pid: Pid::new(0.05, 0.02, 0.0),
+			capture_altitude: 0.0,
+			touchdown_error: None,
+		}
+	}
+}
+
+impl EguiInspect for AutolandPhase {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn lands_and_stops() {
+		let mut gs = GameState::default();
+		gs.plane.body.position = vec2(0.0, 150.0);
+		gs.plane.air = gs.atmosphere.at(150.0);
+		gs.plane.set_trim(&gs.plane.trim(65.0, 0.0).unwrap());
+		gs.engage_autoland(4000.0).unwrap();
+		assert!(gs.plane.gear.down_selected);
+		assert!(gs.autoland.capture_altitude < gs.plane.altitude());
+
+		while gs.autoland.is_engaged() && gs.time() < 200.0 {
+			gs.inner_tick();
+		}
+		assert!(gs.plane.body.velocity.len() < 0.5, "{:?}", gs.plane.body.velocity);
+		let error = gs.autoland.touchdown_error.unwrap();
+		assert!((0.0..300.0).contains(&error), "touched down {error} m from the aim point");
+		let [event] = &gs.events[..] else { panic!("{:?}", gs.events) };
+		assert!(
+			matches!(
+				event.kind,
+				EventKind::Impact {
+					outcome: ImpactOutcome::GoodLanding,
+					autoland: true,
+					..
+				}
+			),
+			"{}",
+			event.message()
+		);
+	}
+
+	#[test]
+	fn throttle_input_gives_up() {
+		let mut gs = GameState::default();
+		gs.plane.body.position = vec2(0.0, 60.0);
+		gs.engage_autoland(1100.0).unwrap();
+		gs.autoland.phase = AutolandPhase::Flare;
+		gs.autoland.pilot_input(false, true, &mut gs.autopilot);
+		assert!(!gs.autoland.is_engaged());
+		assert!(!gs.autopilot.is_engaged() && gs.autopilot.disconnect_warning > 0.0);
+	}
+}
@@ -0,0 +1,305 @@
+use crate::prelude::*;
+
+/// Flies the pitch axis and the throttle in place of the pilot.
+///
+/// Pitch modes are cascaded: an altitude error commands a vertical speed,
+/// a vertical speed error commands a pitch attitude, a pitch error moves the elevator.
+/// Auto-throttle holds indicated airspeed, independently of the pitch mode.
+/// Pilot input on an axis disconnects the autopilot from that axis.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Autopilot {
+	pub pitch_mode: PitchMode,
+	pub autothrottle: bool,
+	/// Body pitch (rad).
+	pub target_pitch: f32,
+	/// m
+	pub target_altitude: f32,
+	/// m/s, positive: climbing.
+	pub target_vertical_speed: f32,
+	/// Indicated airspeed (m/s).
+	pub target_airspeed: f32,
+	/// Vertical speed commanded by altitude hold or path following stays within ± this (m/s).
+	pub max_vertical_speed: f32,
+	/// Pitch commanded by vertical speed hold stays within ± this (rad).
+	pub max_pitch: f32,
+	/// Pitch error (rad) → pitch input.
+	pub pitch_pid: Pid,
+	/// Vertical speed error (m/s) → target pitch (rad).
+	pub vertical_speed_pid: Pid,
+	/// Altitude error (m) → vertical speed (m/s).
+	pub altitude_pid: Pid,
+	/// Airspeed error (m/s) → throttle.
+	pub airspeed_pid: Pid,
+	/// Time left to annunciate the last disconnect (s).
+	#[inspect(hide)]
+	pub disconnect_warning: f32,
+}
+
+/// What the autopilot holds on the pitch axis.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PitchMode {
+	/// Pitch axis flown by the pilot.
+	#[default]
+	Off,
+	Pitch,
+	Altitude,
+	VerticalSpeed,
+	/// Follow a climb or descent path: `target_altitude`, moving at `target_vertical_speed`.
+	/// Both set by the `FlightPlan`.
+	Path,
+}
+
+/// Proportional-integral-derivative controller.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect, Default)]
+#[serde(default)]
+pub struct Pid {
+	pub kp: f32,
+	pub ki: f32,
+	pub kd: f32,
+	/// Accumulated integral term, in units of the output.
+	#[inspect(hide)]
+	pub integral: f32,
+	#[inspect(hide)]
+	pub prev_error: Option<f32>,
+}
+
+impl Autopilot {
+	/// How long a disconnect is annunciated (s).
+	const DISCONNECT_WARNING: f32 = 3.0;
+
+	/// Engage a pitch mode, holding the current pitch, altitude or vertical speed.
+	/// The controllers start from the current pitch input, so the elevator does not jump.
+	pub fn engage(&mut self, mode: PitchMode, plane: &Plane) {
+		self.pitch_mode = mode;
+		self.target_pitch = plane.pitch();
+		self.target_altitude = plane.altitude();
+		self.target_vertical_speed = plane.body.velocity.y();
+		self.pitch_pid.reset(plane.controls.pitch);
+		self.vertical_speed_pid.reset(plane.pitch());
+		self.altitude_pid.reset(0.0);
+	}
+
+	/// Engage auto-throttle, holding the current indicated airspeed.
+	pub fn engage_autothrottle(&mut self, plane: &Plane) {
+		self.autothrottle = true;
+		self.target_airspeed = plane.indicated_airspeed();
+		self.airspeed_pid.reset(plane.engine.throttle);
+	}
+
+	/// The pilot moved the pitch control and/or the throttle: hand those axes back.
+	pub fn pilot_input(&mut self, pitch: bool, throttle: bool) {
+		let pitch = pitch && self.pitch_mode != PitchMode::Off;
+		let throttle = throttle && self.autothrottle;
+		if pitch {
+			self.pitch_mode = PitchMode::Off;
+		}
+		if throttle {
+			self.autothrottle = false;
+		}
+		if pitch || throttle {
+			self.disconnect_warning = Self::DISCONNECT_WARNING;
+		}
+	}
+
+	pub fn is_engaged(&self) -> bool {
+		self.pitch_mode != PitchMode::Off || self.autothrottle
+	}
+
+	/// Move the controls of `plane` towards the targets.
+	pub fn tick(&mut self, dt: f32, plane: &mut Plane) {
+		self.disconnect_warning = (self.disconnect_warning - dt).max(0.0);
+
+		let max = self.max_vertical_speed;
+		let vertical_speed = match self.pitch_mode {
+			PitchMode::Off | PitchMode::Pitch => None,
+			PitchMode::Altitude => {
+				self.target_vertical_speed = self.altitude_pid.update(dt, self.target_altitude - plane.altitude(), -max..=max);
+				Some(self.target_vertical_speed)
+			}
+			PitchMode::VerticalSpeed => Some(self.target_vertical_speed),
+			PitchMode::Path => {
+				let correction = self.altitude_pid.update(dt, self.target_altitude - plane.altitude(), -max..=max);
+				Some((self.target_vertical_speed + correction).clamp(-max, max))
+			}
+		};
+		if let Some(vertical_speed) = vertical_speed {
+			let max = self.max_pitch;
+			self.target_pitch = self.vertical_speed_pid.update(dt, vertical_speed - plane.body.velocity.y(), -max..=max);
+		}
+		if self.pitch_mode != PitchMode::Off {
+			// positive pitch input is nose down
+			plane.controls.pitch = self.pitch_pid.update(dt, wrap_angle(plane.pitch() - self.target_pitch), -1.0..=1.0);
+		}
+
+		if self.autothrottle {
+			plane.engine.throttle = self.airspeed_pid.update(dt, self.target_airspeed - plane.indicated_airspeed(), 0.0..=1.0);
+		}
+	}
+
+	/// Engaged modes and their targets, for the HUD. E.g. "AP ALT 500 m | A/THR 65 m/s".
+	pub fn annunciation(&self) -> Option<String> {
+		let pitch = match self.pitch_mode {
+			PitchMode::Off => None,
+			PitchMode::Pitch => Some(format!("AP PITCH {:+.1}°", self.target_pitch / DEG)),
+			PitchMode::Altitude => Some(format!("AP ALT {:.0} m", self.target_altitude)),
+			PitchMode::VerticalSpeed => Some(format!("AP V/S {:+.1} m/s", self.target_vertical_speed)),
+			PitchMode::Path => Some(format!("AP PATH {:.0} m", self.target_altitude)),
+		};
+		let throttle = self.autothrottle.then(|| format!("A/THR {:.0} m/s", self.target_airspeed));
+		let modes = pitch.into_iter().chain(throttle).join(" | ");
+		(!modes.is_empty()).then_some(modes)
+	}
+}
+
+impl PitchMode {
+	pub const ALL: [Self; 5] = [Self::Off, Self::Pitch, Self::Altitude, Self::VerticalSpeed, Self::Path];
+}
+
+impl Pid {
+	pub fn new(kp: f32, ki: f32, kd: f32) -> Self {
+		Self { kp, ki, kd, ..default() }
+	}
+
+	/// Output for `error` after a time step `dt`, clamped to `range`.
+	/// The integral term is clamped to the range too, so it does not wind up while saturated.
+	pub fn update(&mut self, dt: f32, error: f32, range: std::ops::RangeInclusive<f32>) -> f32 {
+		let (min, max) = (*range.start(), *range.end());
+		let derivative = match self.prev_error {
+			Some(prev) if dt > 0.0 => (error - prev) / dt,
+			_ => 0.0,
+		};
+		self.prev_error = Some(error);
+		self.integral = (self.integral + self.ki * error * dt).clamp(min, max);
+		(self.kp * error + self.integral + self.kd * derivative).clamp(min, max)
+	}
+
+	/// Start over with `output` as the integral term (so the output does not jump for zero error).
+	pub fn reset(&mut self, output: f32) {
+		self.integral = output;
+		self.prev_error = None;
+	}
+}
+
+impl Default for Autopilot {
+	fn default() -> Self {
+		Self {
+			pitch_mode: PitchMode::Off,
+			autothrottle: false,
+			target_pitch: 0.0,
+			target_altitude: 0.0,
+			target_vertical_speed: 0.0,
+			target_airspeed: 0.0,
+			max_vertical_speed: 5.0,
+			max_pitch: 15.0 * DEG,
+			pitch_pid: Pid::new(2.0, 1.0, 0.5),
+			vertical_speed_pid: Pid::new(0.02, 0.005, 0.0),
+			altitude_pid: Pid::new(0.2, 0.0, 0.0),
+			airspeed_pid: Pid::new(0.1, 0.02, 0.0),
+			disconnect_warning: 0.0,
+		}
+	}
+}
+
+impl EguiInspect for PitchMode {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+
+	fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+		inspect_enum_mut(self, &Self::ALL, label, ui)
+	}
+}
+
+#[cfg(test)]
+pub(super) mod test {
+	use super::*;
+
+	/// Trainer flying in still air high above the terrain, for autopilot scenarios.
+	pub struct Flight {
+		pub plane: Plane,
+		/// Time flown (s).
+		pub time: f32,
+		atmosphere: Atmosphere,
+		sky: Tilemap,
+	}
+
+	impl Flight {
+		const DT: f32 = 0.001;
+
+		/// Trimmed for level flight at `altitude` (m) and `airspeed` (m/s).
+		pub fn trimmed(altitude: f32, airspeed: f32) -> Self {
+			let mut plane = Plane::default();
+			plane.body.position = vec2(0.0, altitude);
+			let atmosphere = Atmosphere::default();
+			plane.air = atmosphere.at(altitude);
+			plane.set_trim(&plane.trim(airspeed, 0.0).unwrap());
+			Self {
+				plane,
+				time: 0.0,
+				atmosphere,
+				sky: Tilemap::new(vec2(1, 1), Tile::AIR),
+			}
+		}
+
+		/// Fly one tick with `autopilot` engaged.
+		pub fn tick(&mut self, autopilot: &mut Autopilot) {
+			self.plane.air = self.atmosphere.at(self.plane.altitude());
+			autopilot.tick(Self::DT, &mut self.plane);
+			self.plane.tick(Self::DT, &self.sky, Integrator::RK4);
+			self.time += Self::DT;
+		}
+	}
+
+	#[test]
+	fn holds_altitude_and_airspeed() {
+		let mut flight = Flight::trimmed(500.0, 65.0);
+		let mut autopilot = Autopilot::default();
+		autopilot.engage(PitchMode::Altitude, &flight.plane);
+		autopilot.engage_autothrottle(&flight.plane);
+		autopilot.target_altitude = 520.0;
+		autopilot.target_airspeed += 3.0;
+
+		while flight.time < 40.0 {
+			flight.tick(&mut autopilot);
+		}
+		let plane = &flight.plane;
+		assert!((plane.altitude() - 520.0).abs() < 2.0, "altitude {}", plane.altitude());
+		assert!((plane.indicated_airspeed() - autopilot.target_airspeed).abs() < 1.0, "airspeed {}", plane.indicated_airspeed());
+		assert!(plane.body.velocity.y().abs() < 0.5, "climb {}", plane.body.velocity.y());
+
+		autopilot.pilot_input(true, false);
+		assert_eq!(autopilot.pitch_mode, PitchMode::Off);
+		assert!(autopilot.autothrottle && autopilot.disconnect_warning > 0.0);
+	}
+
+	#[test]
+	fn holds_altitude_with_a_slow_elevator() {
+		let mut flight = Flight::trimmed(500.0, 65.0);
+		let elevator = flight.plane.surfaces.iter().position(|s| s.control == ControlAxis::Pitch).unwrap();
+		let servo = Actuator {
+			max_rate: 10.0 * DEG,
+			time_constant: 0.2,
+			..flight.plane.surfaces[elevator].actuator.clone()
+		};
+		flight.plane.surfaces[elevator].actuator = servo.clone();
+		let mut autopilot = Autopilot::default();
+		autopilot.engage(PitchMode::Altitude, &flight.plane);
+		autopilot.target_altitude = 520.0;
+
+		// the step asks for more than the servo can give: it runs at its rate limit, and lags behind
+		let (mut max_rate, mut max_lag) = (0.0f32, 0.0f32);
+		while flight.time < 25.0 {
+			let deflection = flight.plane.surfaces[elevator].deflection;
+			flight.tick(&mut autopilot);
+			let surface = &flight.plane.surfaces[elevator];
+			max_rate = max_rate.max((surface.deflection - deflection).abs() / Flight::DT);
+			max_lag = max_lag.max((surface.commanded_deflection(&flight.plane.controls) - surface.deflection).abs());
+		}
+		let plane = &flight.plane;
+		assert!(max_rate > 0.95 * servo.max_rate, "elevator moved at {} °/s", max_rate / DEG);
+		assert!(max_lag > 2.0 * DEG, "elevator lagged {}°", max_lag / DEG);
+		assert!((plane.altitude() - 520.0).abs() < 1.0, "altitude {}", plane.altitude());
+		assert!(plane.body.velocity.y().abs() < 0.5, "climb {}", plane.body.velocity.y());
+	}
+}
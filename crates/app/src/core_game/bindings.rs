@@ -0,0 +1,186 @@
+use crate::prelude::*;
+
+type Key = egui::Key;
+
+/// Something the pilot can do with a key. Bound to keys in `Controls::bindings`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+	/// Stick forward, held.
+	NoseDown,
+	/// Stick back, held.
+	NoseUp,
+	ThrottleUp,
+	ThrottleDown,
+	TrimNoseDown,
+	TrimNoseUp,
+	/// Wheel brakes, while held.
+	Brakes,
+	/// Toggles, once per key press.
+	Gear,
+	ParkingBrake,
+	Pause,
+}
+
+/// A control moved continuously by a pair of actions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+	/// `ControlInputs::pitch`, positive: nose down.
+	Pitch,
+	/// `Engine::throttle`.
+	Throttle,
+	/// `ControlInputs::pitch_trim`, positive: nose down.
+	PitchTrim,
+}
+
+impl Action {
+	pub const ALL: [Self; 10] = [
+		Self::NoseDown,
+		Self::NoseUp,
+		Self::ThrottleUp,
+		Self::ThrottleDown,
+		Self::TrimNoseDown,
+		Self::TrimNoseUp,
+		Self::Brakes,
+		Self::Gear,
+		Self::ParkingBrake,
+		Self::Pause,
+	];
+
+	/// Triggered on the frame a key goes down, rather than every frame it is held.
+	pub fn is_edge_triggered(self) -> bool {
+		matches!(self, Self::Gear | Self::ParkingBrake | Self::Pause)
+	}
+
+	pub fn default_keys(self) -> Vec<Key> {
+		match self {
+			Self::NoseDown => vec![Key::ArrowUp, Key::E],
+			Self::NoseUp => vec![Key::ArrowDown, Key::D],
+			Self::ThrottleUp => vec![Key::ArrowRight, Key::F],
+			Self::ThrottleDown => vec![Key::ArrowLeft, Key::S],
+			Self::TrimNoseDown => vec![Key::PageUp],
+			Self::TrimNoseUp => vec![Key::PageDown],
+			Self::Brakes => vec![Key::B],
+			Self::Gear => vec![Key::G],
+			Self::ParkingBrake => vec![Key::P],
+			Self::Pause => vec![Key::Space],
+		}
+	}
+
+	/// For the rebinding UI.
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::NoseDown => "nose down",
+			Self::NoseUp => "nose up",
+			Self::ThrottleUp => "throttle up",
+			Self::ThrottleDown => "throttle down",
+			Self::TrimNoseDown => "trim nose down",
+			Self::TrimNoseUp => "trim nose up",
+			Self::Brakes => "brakes",
+			Self::Gear => "gear",
+			Self::ParkingBrake => "parking brake",
+			Self::Pause => "pause",
+		}
+	}
+}
+
+impl Axis {
+	/// Actions decreasing and increasing the axis.
+	pub fn actions(self) -> [Action; 2] {
+		match self {
+			Self::Pitch => [Action::NoseUp, Action::NoseDown],
+			Self::Throttle => [Action::ThrottleDown, Action::ThrottleUp],
+			Self::PitchTrim => [Action::TrimNoseUp, Action::TrimNoseDown],
+		}
+	}
+}
+
+impl Controls {
+	pub fn keys(&self, action: Action) -> &[Key] {
+		self.bindings.get(&action).map_or(&[], Vec::as_slice)
+	}
+
+	/// Whether `action` is triggered this frame: a key just pressed for edge-triggered actions, held otherwise.
+	pub fn triggered(&self, action: Action, inputs: &Inputs) -> bool {
+		let keys = match action.is_edge_triggered() {
+			true => &inputs.keys_just_pressed,
+			false => &inputs.keys_down,
+		};
+		self.keys(action).iter().any(|k| keys.contains(k))
+	}
+
+	/// -1, 0 or 1: direction in which keys move `axis` (0 if none or both directions are held).
+	pub fn axis(&self, axis: Axis, inputs: &Inputs) -> f32 {
+		let [decrease, increase] = axis.actions().map(|a| self.triggered(a, inputs) as i32 as f32);
+		increase - decrease
+	}
+
+	/// Bind `key` to `action`, removing it from any other action so that each key does one thing.
+	pub fn bind(&mut self, action: Action, key: Key) {
+		for keys in self.bindings.values_mut() {
+			keys.retain(|&k| k != key);
+		}
+		self.bindings.entry(action).or_default().push(key);
+	}
+
+	pub fn unbind(&mut self, action: Action, key: Key) {
+		if let Some(keys) = self.bindings.get_mut(&action) {
+			keys.retain(|&k| k != key);
+		}
+	}
+
+	/// Change per second while a key is held, and return towards 0 per second without (0: stays).
+	pub fn rates(&self, axis: Axis) -> (f32, f32) {
+		match axis {
+			Axis::Pitch => (self.pitch_rate, self.pitch_centering),
+			Axis::Throttle => (self.throttle_rate, 0.0),
+			Axis::PitchTrim => (self.trim_rate, 0.0),
+		}
+	}
+
+	/// Move `value` where the keys of `axis` push it, within `range`, for a frame of `dt` seconds.
+	/// Returns whether keys moved it.
+	pub fn move_axis(&self, axis: Axis, inputs: &Inputs, dt: f32, range: std::ops::RangeInclusive<f32>, value: &mut f32) -> bool {
+		let (rate, centering) = self.rates(axis);
+		let direction = self.axis(axis, inputs);
+		if direction != 0.0 {
+			*value += direction * rate * dt;
+		} else {
+			let step = centering * dt;
+			*value -= value.clamp(-step, step);
+		}
+		*value = value.clamp(*range.start(), *range.end());
+		direction != 0.0
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn bindings_and_axes() {
+		let mut controls = Controls::default();
+		let mut inputs = Inputs::default();
+		inputs.keys_down.insert(Key::ArrowUp);
+		assert_eq!(controls.axis(Axis::Pitch, &inputs), 1.0);
+
+		controls.pitch_rate = 2.0;
+		controls.pitch_centering = 1.0;
+		let mut pitch = 0.0;
+		assert!(controls.move_axis(Axis::Pitch, &inputs, 0.1, -1.0..=1.0, &mut pitch));
+		assert!((pitch - 0.2).abs() < 1e-6);
+		inputs.keys_down.clear();
+		assert!(!controls.move_axis(Axis::Pitch, &inputs, 0.1, -1.0..=1.0, &mut pitch));
+		assert!((pitch - 0.1).abs() < 1e-6);
+
+		// held Space pauses once, not every frame
+		inputs.keys_down.insert(Key::Space);
+		assert!(!controls.triggered(Action::Pause, &inputs));
+		inputs.keys_just_pressed.insert(Key::Space);
+		assert!(controls.triggered(Action::Pause, &inputs));
+
+		controls.bind(Action::Gear, Key::Space);
+		assert!(controls.keys(Action::Pause).is_empty());
+		assert_eq!(controls.keys(Action::Gear), [Key::G, Key::Space]);
+	}
+}
@@ -0,0 +1,247 @@
+use crate::prelude::*;
+
+/// Part of the aircraft that can touch the terrain.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HullPart {
+	Wheel,
+	Fuselage,
+	Tail,
+	Propeller,
+}
+
+/// Vertex of the airframe's convex collision hull.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect)]
+pub struct HullPoint {
+	/// Relative to the datum (m).
+	pub pos: vec2f,
+	pub part: HullPart,
+}
+
+/// A hull point or wheel touching the terrain during the last tick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactPoint {
+	pub part: HullPart,
+	/// Velocity into the terrain, along the surface normal (m/s).
+	pub sink_rate: f32,
+	/// Wheel locked by the brakes, sliding over the surface.
+	pub skidding: bool,
+}
+
+/// How a touchdown or collision ended.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImpactOutcome {
+	GoodLanding,
+	HardLanding,
+	/// Touched down on the fuselage, without wheels.
+	BellyLanding,
+	TailStrike,
+	PropStrike,
+	GearCollapse,
+	Destroyed,
+}
+
+/// Peak values seen from the first contact until the impact is assessed.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect)]
+pub struct Impact {
+	/// s
+	pub start_time: f32,
+	/// Peak sink rate at the wheels (m/s).
+	pub wheel_sink_rate: f32,
+	/// Peak sink rate at any other hull point (m/s).
+	pub hull_sink_rate: f32,
+	/// Peak load factor (g).
+	pub load_factor: f32,
+	pub wheel_contact: bool,
+	pub fuselage_contact: bool,
+	pub tail_contact: bool,
+	pub prop_contact: bool,
+}
+
+/// Thresholds for classifying an impact.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct CrashLimits {
+	/// Wheel sink rate above which a landing is hard (m/s).
+	pub hard_landing_sink_rate: f32,
+	pub hard_landing_load_factor: f32,
+	/// Wheel sink rate the gear can absorb (m/s).
+	pub gear_sink_rate: f32,
+	pub gear_load_factor: f32,
+	/// Airframe sink rate / load factor beyond which the aircraft is destroyed.
+	pub destroyed_sink_rate: f32,
+	pub destroyed_load_factor: f32,
+	/// Time from first contact until the impact is assessed (s).
+	pub assessment_time: f32,
+}
+
+/// Watches terrain contacts and classifies each touchdown or collision.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct CrashDetector {
+	pub limits: CrashLimits,
+	/// Impact being assessed.
+	#[inspect(hide)]
+	pub impact: Option<Impact>,
+	/// Hull parts in contact during the previous tick.
+	#[inspect(hide)]
+	pub touching: Vec<HullPart>,
+	/// Last time anything touched the terrain (s).
+	#[inspect(hide)]
+	pub last_contact_time: f32,
+}
+
+impl CrashDetector {
+	/// Contacts shortly after lift-off belong to the takeoff, not a landing (s).
+	const MIN_AIRBORNE_TIME: f32 = 1.0;
+
+	/// Update with the contacts of the last tick. Returns the outcome once an impact has been assessed.
+	pub fn tick(&mut self, time: f32, contacts: &[ContactPoint], load_factor: f32) -> Option<(Impact, ImpactOutcome)> {
+		// a new impact starts on touchdown after flying for a while,
+		// or when any part other than the wheels hits the terrain (e.g. tail strike during rotation)
+		let touchdown = !contacts.is_empty() && time - self.last_contact_time > Self::MIN_AIRBORNE_TIME;
+		let strike = contacts.iter().any(|c| c.part != HullPart::Wheel && !self.touching.contains(&c.part));
+		self.touching = contacts.iter().map(|c| c.part).collect();
+		if !contacts.is_empty() {
+			self.last_contact_time = time;
+		}
+
+		if self.impact.is_none() && (touchdown || strike) {
+			self.impact = Some(Impact {
+				start_time: time,
+				wheel_sink_rate: 0.0,
+				hull_sink_rate: 0.0,
+				load_factor: 0.0,
+				wheel_contact: false,
+				fuselage_contact: false,
+				tail_contact: false,
+				prop_contact: false,
+			});
+		}
+
+		let impact = self.impact.as_mut()?;
+		impact.load_factor = impact.load_factor.max(load_factor);
+		for contact in contacts {
+			match contact.part {
+				HullPart::Wheel => {
+					impact.wheel_contact = true;
+					impact.wheel_sink_rate = impact.wheel_sink_rate.max(contact.sink_rate)
+				}
+				part => {
+					impact.hull_sink_rate = impact.hull_sink_rate.max(contact.sink_rate);
+					match part {
+						HullPart::Tail => impact.tail_contact = true,
+						HullPart::Propeller => impact.prop_contact = true,
+						_ => impact.fuselage_contact = true,
+					}
+				}
+			}
+		}
+
+		if time - impact.start_time < self.limits.assessment_time {
+			return None;
+		}
+		let impact = self.impact.take()?;
+		Some((impact, self.limits.classify(&impact)))
+	}
+}
+
+impl CrashLimits {
+	pub fn classify(&self, impact: &Impact) -> ImpactOutcome {
+		use ImpactOutcome::*;
+		let hull_touched = impact.fuselage_contact || impact.tail_contact || impact.prop_contact;
+		if impact.load_factor > self.destroyed_load_factor || (hull_touched && impact.hull_sink_rate > self.destroyed_sink_rate) {
+			Destroyed
+		} else if impact.wheel_sink_rate > self.gear_sink_rate || impact.load_factor > self.gear_load_factor {
+			GearCollapse
+		} else if impact.fuselage_contact && !impact.wheel_contact {
+			BellyLanding
+		} else if impact.prop_contact {
+			PropStrike
+		} else if impact.tail_contact {
+			TailStrike
+		} else if impact.fuselage_contact || impact.wheel_sink_rate > self.hard_landing_sink_rate || impact.load_factor > self.hard_landing_load_factor {
+			HardLanding
+		} else {
+			GoodLanding
+		}
+	}
+}
+
+impl ImpactOutcome {
+	/// Aircraft can no longer fly.
+	pub fn is_crash(self) -> bool {
+		matches!(self, Self::GearCollapse | Self::Destroyed)
+	}
+
+	pub fn description(self) -> &'static str {
+		match self {
+			Self::GoodLanding => "good landing",
+			Self::HardLanding => "hard landing",
+			Self::BellyLanding => "belly landing",
+			Self::TailStrike => "tail strike",
+			Self::PropStrike => "prop strike",
+			Self::GearCollapse => "gear collapse",
+			Self::Destroyed => "destroyed",
+		}
+	}
+}
+
+impl Default for CrashLimits {
+	fn default() -> Self {
+		Self {
+			hard_landing_sink_rate: 2.0,
+			hard_landing_load_factor: 2.5,
+			gear_sink_rate: 4.0,
+			gear_load_factor: 4.5,
+			destroyed_sink_rate: 8.0,
+			destroyed_load_factor: 10.0,
+			assessment_time: 0.5,
+		}
+	}
+}
+
+impl Default for CrashDetector {
+	fn default() -> Self {
+		Self {
+			limits: default(),
+			impact: None,
+			touching: vec![],
+			last_contact_time: 0.0,
+		}
+	}
+}
+
+impl EguiInspect for HullPart {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn touchdown(contacts: &[ContactPoint], load_factor: f32) -> ImpactOutcome {
+		let mut detector = CrashDetector {
+			last_contact_time: -10.0,
+			..default()
+		};
+		assert!(detector.tick(0.0, contacts, load_factor).is_none());
+		detector.tick(0.5, contacts, load_factor).expect("assessed").1
+	}
+
+	#[test]
+	fn classify_impacts() {
+		let contact = |part, sink_rate| ContactPoint { part, sink_rate, skidding: false };
+		let wheel = |sink_rate| contact(HullPart::Wheel, sink_rate);
+		assert_eq!(touchdown(&[wheel(0.5)], 1.2), ImpactOutcome::GoodLanding);
+		assert_eq!(touchdown(&[wheel(3.0)], 1.5), ImpactOutcome::HardLanding);
+		assert_eq!(touchdown(&[wheel(5.0)], 1.5), ImpactOutcome::GearCollapse);
+		let tail = contact(HullPart::Tail, 0.5);
+		assert_eq!(touchdown(&[wheel(0.5), tail], 1.2), ImpactOutcome::TailStrike);
+		let fuselage = contact(HullPart::Fuselage, 20.0);
+		assert_eq!(touchdown(&[fuselage], 3.0), ImpactOutcome::Destroyed);
+		let belly = contact(HullPart::Fuselage, 1.0);
+		assert_eq!(touchdown(&[belly, contact(HullPart::Propeller, 1.0)], 1.5), ImpactOutcome::BellyLanding);
+	}
+}
@@ -13,7 +13,15 @@ impl GameState {
 		}
 
 		self.draw_tilemap_3d(sg);
-		self.plane.draw(sg)
+
+		let pixel = 1.0 / self.camera.zoom;
+		let following_plan = self.autopilot.pitch_mode == PitchMode::Path && !self.autoland.is_engaged();
+		self.flight_plan.draw(sg, pixel, following_plan.then(|| self.plane.position().x()));
+		self.autoland.draw(sg, pixel);
+
+		let mut plane = self.plane.clone();
+		(plane.body.position, plane.body.rotation) = self.render_pose();
+		plane.draw(sg)
 	}
 
 	pub fn draw_tilemap_3d(&self, sg: &mut Scenegraph) {
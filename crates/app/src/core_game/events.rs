@@ -0,0 +1,46 @@
+use crate::prelude::*;
+
+/// Something noteworthy that happened during the simulation, reported in the UI.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GameEvent {
+	/// Simulated time (s).
+	pub time: f32,
+	pub kind: EventKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum EventKind {
+	Impact {
+		outcome: ImpactOutcome,
+		impact: Impact,
+		/// Flown by the `Autoland` rather than the pilot.
+		#[serde(default)]
+		autoland: bool,
+	},
+}
+
+impl GameEvent {
+	pub fn message(&self) -> String {
+		let time = self.time;
+		match &self.kind {
+			EventKind::Impact { outcome, impact, autoland } => {
+				let sink_rate = impact.wheel_sink_rate.max(impact.hull_sink_rate);
+				let by = if *autoland { " [autoland]" } else { "" };
+				format!("{time:.1} s: {}{by} ({sink_rate:.1} m/s, {:.1} g)", outcome.description(), impact.load_factor)
+			}
+		}
+	}
+
+	/// Should be shown in red.
+	pub fn is_alert(&self) -> bool {
+		match &self.kind {
+			EventKind::Impact { outcome, .. } => *outcome != ImpactOutcome::GoodLanding,
+		}
+	}
+}
+
+impl EguiInspect for GameEvent {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		ui.label(format!("{label}: {}", self.message()));
+	}
+}
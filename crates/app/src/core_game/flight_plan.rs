@@ -0,0 +1,279 @@
+use crate::prelude::*;
+
+/// Waypoints for the autopilot to fly over, each with an altitude and airspeed to reach there.
+///
+/// Flight plans are flown towards +x (where the aircraft points at zero pitch),
+/// so waypoints are kept in order of x and the active one is the first ahead of the aircraft.
+/// Between waypoints, climbs start right away and descents as late as possible,
+/// at fixed angles so that the aircraft can follow at its planned speed.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct FlightPlan {
+	pub waypoints: Vec<Waypoint>,
+	/// Where the plan was engaged: start of the first leg.
+	pub origin: vec2f,
+	/// Planned climb angle over the ground (rad). Must be within the aircraft's climb performance.
+	pub climb_angle: f32,
+	/// Planned descent angle over the ground (rad). Must be well within the aircraft's glide (see `DESCENT_MARGIN`).
+	pub descent_angle: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect, PartialEq)]
+pub struct Waypoint {
+	/// x and altitude (m).
+	pub position: vec2f,
+	/// Indicated airspeed (m/s), from the previous waypoint onwards.
+	pub airspeed: f32,
+}
+
+impl FlightPlan {
+	/// Descents may be at most this fraction of the aircraft's idle glide angle at the planned airspeed,
+	/// leaving auto-throttle room to hold the airspeed while catching up with the path.
+	pub const DESCENT_MARGIN: f32 = 0.5;
+
+	/// Insert a waypoint, keeping them in order of x. Returns its index.
+	pub fn add(&mut self, waypoint: Waypoint) -> usize {
+		let i = self.waypoints.partition_point(|w| w.position.x() <= waypoint.position.x());
+		self.waypoints.insert(i, waypoint);
+		i
+	}
+
+	/// Restore the order of x after editing.
+	pub fn sort(&mut self) {
+		self.waypoints.sort_by(|a, b| a.position.x().total_cmp(&b.position.x()));
+	}
+
+	/// Index of the waypoint closest to `pos`, if within `radius` (m).
+	pub fn nearest(&self, pos: vec2f, radius: f32) -> Option<usize> {
+		let distance = |w: &Waypoint| (w.position - pos).len();
+		let (i, w) = self.waypoints.iter().enumerate().min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))?;
+		(distance(w) <= radius).then_some(i)
+	}
+
+	/// Index of the waypoint being flown to from `x`: the first one ahead.
+	pub fn active(&self, x: f32) -> Option<usize> {
+		self.waypoints.iter().position(|w| w.position.x() > x)
+	}
+
+	/// Start of the leg to waypoint `i`: the previous waypoint, or the origin.
+	pub fn leg_start(&self, i: usize) -> vec2f {
+		match i {
+			0 => self.origin,
+			i => self.waypoints[i - 1].position,
+		}
+	}
+
+	/// Planned altitude (m) at `x` on the leg from `from` to `to`, and its slope (altitude change per m of x).
+	pub fn profile(&self, from: vec2f, to: &Waypoint, x: f32) -> (f32, f32) {
+		let (target, start) = (to.position.y(), from.y());
+		if target >= start {
+			let climb = self.climb_angle.tan();
+			let altitude = start + climb * (x - from.x()).max(0.0);
+			if altitude < target {
+				(altitude, climb)
+			} else {
+				(target, 0.0)
+			}
+		} else {
+			let descent = self.descent_angle.tan();
+			let altitude = target + descent * (to.position.x() - x).max(0.0);
+			if altitude < start {
+				(altitude, -descent)
+			} else {
+				(start, 0.0)
+			}
+		}
+	}
+
+	/// Points where the profile from `from` to `to` changes slope: start, top of climb or descent, end.
+	fn leg_points(&self, from: vec2f, to: &Waypoint) -> [vec2f; 3] {
+		let height = to.position.y() - from.y();
+		let turn = match height >= 0.0 {
+			true => from.x() + height / self.climb_angle.tan().max(f32::EPSILON),
+			false => to.position.x() + height / self.descent_angle.tan().max(f32::EPSILON),
+		};
+		let turn = turn.clamp(from.x(), to.position.x());
+		[from.x(), turn, to.position.x()].map(|x| vec2(x, self.profile(from, to, x).0))
+	}
+
+	/// Take over the autopilot's targets, engaging path following and auto-throttle.
+	/// Fails if a descent ahead is too steep for the aircraft to hold its airspeed (see `DESCENT_MARGIN`).
+	pub fn engage(&mut self, autopilot: &mut Autopilot, plane: &Plane) -> Result<()> {
+		let Some(active) = self.active(plane.position().x()) else { bail!("no waypoint ahead") };
+		self.origin = vec2(plane.position().x(), plane.altitude());
+		for i in active..self.waypoints.len() {
+			let waypoint = &self.waypoints[i];
+			if waypoint.position.y() >= self.leg_start(i).y() {
+				continue;
+			}
+			let true_airspeed = waypoint.airspeed / plane.air.indicated_airspeed(1.0);
+			let steepest = plane.steepest_descent(true_airspeed).with_context(|| format!("WPT {}", i + 1))?;
+			if self.descent_angle > Self::DESCENT_MARGIN * steepest {
+				bail!(
+					"WPT {}: can't descend at {:.1}° and {:.0} m/s, glides at {:.1}° at idle",
+					i + 1,
+					self.descent_angle / DEG,
+					waypoint.airspeed,
+					steepest / DEG
+				);
+			}
+		}
+		autopilot.engage(PitchMode::Path, plane);
+		autopilot.engage_autothrottle(plane);
+		Ok(())
+	}
+
+	/// Set the autopilot's targets for the aircraft's position on the plan, while following it.
+	/// After the last waypoint, hold its altitude.
+	pub fn tick(&self, plane: &Plane, autopilot: &mut Autopilot) {
+		if autopilot.pitch_mode != PitchMode::Path {
+			return;
+		}
+		let x = plane.position().x();
+		match self.active(x) {
+			Some(i) => {
+				let waypoint = &self.waypoints[i];
+				let (altitude, slope) = self.profile(self.leg_start(i), waypoint, x);
+				autopilot.target_altitude = altitude;
+				autopilot.target_vertical_speed = slope * plane.body.velocity.x();
+				autopilot.target_airspeed = waypoint.airspeed;
+			}
+			None => {
+				if let Some(last) = self.waypoints.last() {
+					autopilot.target_altitude = last.position.y();
+				}
+				autopilot.pitch_mode = PitchMode::Altitude;
+			}
+		}
+	}
+
+	/// Next waypoint and distance to go, for the HUD. E.g. "WPT 2/3: 800 m, 60 m/s in 1.2 km".
+	pub fn status(&self, x: f32) -> Option<String> {
+		let i = self.active(x)?;
+		let w = &self.waypoints[i];
+		let distance = w.position.x() - x;
+		Some(format!(
+			"WPT {}/{}: {:.0} m, {:.0} m/s in {:.1} km",
+			i + 1,
+			self.waypoints.len(),
+			w.position.y(),
+			w.airspeed,
+			distance / 1000.0
+		))
+	}
+
+	/// Waypoint markers and the planned profile. `pixel`: size of a screen pixel in m, so that markers keep their size when zooming.
+	/// `engaged_at`: x of the aircraft while following the plan, to draw the leg from the origin.
+	pub fn draw(&self, sg: &mut Scenegraph, pixel: f32, engaged_at: Option<f32>) {
+		const Z: f32 = 1.5;
+		let vertex = |p: vec2f| TerrainVertex::new(p.append(Z)).with(|v| v.color = pack4xu8(MAGENTA));
+
+		let first = match engaged_at.and_then(|x| self.active(x)) {
+			Some(0) => 0,
+			_ => 1,
+		};
+		for i in first..self.waypoints.len() {
+			let points = self.leg_points(self.leg_start(i), &self.waypoints[i]);
+			for (a, b) in iter::zip(points, &points[1..]) {
+				if (*b - a).len() < f32::EPSILON {
+					continue;
+				}
+				let normal = 1.5 * pixel * vec2(a.y() - b.y(), b.x() - a.x()).normalized();
+				sg.meshbuffer.push_rect(&[a - normal, *b - normal, *b + normal, a + normal].map(vertex));
+			}
+		}
+
+		let size = 8.0 * pixel;
+		for w in &self.waypoints {
+			let p = w.position;
+			let diamond = [vec2(0.0, -size), vec2(size, 0.0), vec2(0.0, size), vec2(-size, 0.0)];
+			sg.meshbuffer.push_rect(&diamond.map(|d| vertex(p + d)));
+		}
+	}
+}
+
+impl Waypoint {
+	pub fn new(position: vec2f, airspeed: f32) -> Self {
+		Self { position, airspeed }
+	}
+}
+
+impl Default for FlightPlan {
+	fn default() -> Self {
+		Self {
+			waypoints: vec![],
+			origin: vec::ZERO,
+			climb_angle: 2.0 * DEG,
+			descent_angle: 1.5 * DEG,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::core_game::autopilot::test::Flight;
+
+	#[test]
+	fn climbs_early_and_descends_late() {
+		let plan = FlightPlan::default();
+		let from = vec2(0.0, 500.0);
+		let high = Waypoint::new(vec2(5000.0, 600.0), 65.0);
+		assert_eq!(plan.profile(from, &high, 0.0), (500.0, plan.climb_angle.tan()));
+		assert_eq!(plan.profile(from, &high, 4000.0), (600.0, 0.0));
+
+		let low = Waypoint::new(vec2(5000.0, 400.0), 65.0);
+		assert_eq!(plan.profile(from, &low, 1000.0), (500.0, 0.0));
+		let (altitude, slope) = plan.profile(from, &low, 4900.0);
+		assert!((altitude - (400.0 + 100.0 * plan.descent_angle.tan())).abs() < 1e-3 && slope < 0.0);
+
+		let [_, top, end] = plan.leg_points(from, &low);
+		assert!((top.y() - 500.0).abs() < 1e-3 && end == low.position, "{top:?}");
+	}
+
+	#[test]
+	fn follows_plan() {
+		let mut flight = Flight::trimmed(500.0, 65.0);
+		let mut plan = FlightPlan::default();
+		plan.add(Waypoint::new(vec2(3400.0, 470.0), 68.0));
+		plan.add(Waypoint::new(vec2(1000.0, 530.0), 62.0));
+		let mut autopilot = Autopilot::default();
+		plan.engage(&mut autopilot, &flight.plane).unwrap();
+		let [_, top_of_descent, _] = plan.leg_points(plan.waypoints[0].position, &plan.waypoints[1]);
+		assert!(top_of_descent.x() - 1000.0 < 200.0, "descends all the way: {top_of_descent:?}");
+
+		// stop shortly after the last waypoint
+		let mut passed = vec![];
+		while passed.len() < 2 || flight.plane.position().x() < 3500.0 {
+			let active = plan.active(flight.plane.position().x());
+			plan.tick(&flight.plane, &mut autopilot);
+			flight.tick(&mut autopilot);
+			if plan.active(flight.plane.position().x()) != active {
+				passed.push((flight.plane.altitude(), flight.plane.indicated_airspeed()));
+			}
+			assert!(flight.time < 70.0, "{passed:?}");
+		}
+		for ((altitude, airspeed), w) in iter::zip(passed, &plan.waypoints) {
+			assert!((altitude - w.position.y()).abs() < 5.0, "altitude {altitude} at {w:?}");
+			assert!((airspeed - w.airspeed).abs() < 2.0, "airspeed {airspeed} at {w:?}");
+		}
+		assert_eq!(autopilot.pitch_mode, PitchMode::Altitude);
+	}
+
+	#[test]
+	fn rejects_descent_steeper_than_the_glide() {
+		let flight = Flight::trimmed(500.0, 65.0);
+		let mut plan = FlightPlan {
+			descent_angle: 6.0 * DEG,
+			..default()
+		};
+		plan.add(Waypoint::new(vec2(3000.0, 400.0), 68.0));
+		let mut autopilot = Autopilot::default();
+		let err = plan.engage(&mut autopilot, &flight.plane).unwrap_err();
+		assert!(err.to_string().starts_with("WPT 1: can't descend"), "{err:#}");
+		assert!(!autopilot.is_engaged());
+
+		plan.descent_angle = FlightPlan::default().descent_angle;
+		plan.engage(&mut autopilot, &flight.plane).unwrap();
+	}
+}
@@ -24,8 +24,26 @@ pub struct GameState {
 	pub mouse_pos: vec2f,
 
 	pub debug: DebugOpts,
+	/// Key bindings, from the settings. Not saved with the game.
+	#[serde(skip)]
+	pub controls: Controls,
+	/// Keys go to the UI (e.g. being bound to an action) rather than flying the aircraft.
+	#[serde(skip)]
+	#[inspect(hide)]
+	pub keys_captured: bool,
 
 	pub plane: Plane,
+	pub autopilot: Autopilot,
+	pub flight_plan: FlightPlan,
+	pub autoland: Autoland,
+
+	pub atmosphere: Atmosphere,
+	pub wind: WindField,
+	pub variometer: Variometer,
+
+	pub crash_detector: CrashDetector,
+	/// Landings, crashes, ... most recent last.
+	pub events: Vec<GameEvent>,
 
 	#[serde(skip)]
 	pub tilemap: Tilemap,
@@ -35,6 +53,14 @@ pub struct GameState {
 
 	#[inspect(hide)]
 	pub plotter: Plotter,
+
+	/// Simulated time (s) still to be run in fixed physics steps.
+	#[inspect(hide)]
+	pub time_accumulator: f32,
+	/// Plane position and pitch before the last physics step, for render interpolation.
+	#[serde(skip)]
+	#[inspect(hide)]
+	pub prev_pose: (vec2f, f32),
 }
 
 impl GameState {
@@ -45,37 +71,136 @@ impl GameState {
 		self.tick_inputs();
 
 		if self.debug.pause_all_systems {
-			self.plane.tick(0.0, &self.tilemap);
+			self.time_accumulator = 0.0;
+			self.prev_pose = (self.plane.position(), self.plane.pitch());
+			self.update_plane_air();
+			self.plane.tick(0.0, &self.tilemap, self.debug.integrator);
 			if self.debug.force_record_plots {
 				self.record_plot()
 			}
 		} else {
-			// TODO: this assumes 60 FPS
-			for _ in 0..(16 * self.debug.timepassage) {
-				self.inner_tick()
-			}
+			self.run_physics_steps();
 			self.record_plot()
 		}
 
 		self.tick_camera();
 
-		if self.plane.position().y() < 0.0 {
-			self.debug.pause_all_systems = true; // stop simulation on crash
-		}
-
 		self.last_frame_cpu_micros = (micros_since_epoch() - self.last_frame_micro_timestamp).try_into().expect("u32 overflow");
 	}
 
+	/// Fixed physics timestep (s).
+	pub const DT: f32 = 0.001;
+	/// At most this much wall-clock time is simulated per frame (s).
+	/// Longer frames (hiccups, debugger breaks) are dropped rather than caught up,
+	/// else a slow frame causes more steps next frame, which makes it even slower, ...
+	pub const MAX_FRAME_TIME: f32 = 0.1;
+
+	/// Run as many fixed steps as needed to keep up with wall-clock time (scaled by `timepassage`).
+	fn run_physics_steps(&mut self) {
+		let frame_time = (self.last_frame_micros as f32 / 1e6).min(Self::MAX_FRAME_TIME);
+		self.time_accumulator += frame_time * self.debug.timepassage.max(0.0);
+		let max_steps = (Self::MAX_FRAME_TIME * self.debug.timepassage / Self::DT).ceil() as u32;
+
+		let mut steps = 0;
+		while self.time_accumulator >= Self::DT {
+			if steps >= max_steps {
+				self.time_accumulator = 0.0; // can't keep up: drop the backlog
+				break;
+			}
+			self.inner_tick();
+			self.time_accumulator -= Self::DT;
+			steps += 1;
+			if self.debug.pause_all_systems {
+				self.time_accumulator = 0.0; // crashed
+				break;
+			}
+		}
+	}
+
 	pub fn inner_tick(&mut self) {
-		// fixed 1ms physics timestep
-		self.plane.tick(0.001 /*dt*/, &self.tilemap);
+		let dt = Self::DT;
+		self.prev_pose = (self.plane.position(), self.plane.pitch());
+		self.wind.tick(dt, self.plane.true_airspeed(), self.time());
+		self.update_plane_air();
+		if self.autoland.is_engaged() {
+			self.autoland.tick(dt, &mut self.plane, &mut self.autopilot);
+		} else {
+			self.flight_plan.tick(&self.plane, &mut self.autopilot);
+		}
+		self.autopilot.tick(dt, &mut self.plane);
+		self.plane.tick(dt, &self.tilemap, self.debug.integrator);
+		let body = &self.plane.body;
+		self.variometer.tick(dt, body.velocity.y(), self.plane.air_velocity(), body.acceleration, self.plane.gravity);
+		self.tick_crash_detector();
 		self.frame += 1;
 		self.record_plot();
 	}
 
+	fn tick_crash_detector(&mut self) {
+		let time = self.time();
+		if let Some((impact, outcome)) = self.crash_detector.tick(time, &self.plane.contacts.borrow(), self.plane.load_factor()) {
+			let autoland = self.autoland.is_engaged();
+			self.events.push(GameEvent {
+				time,
+				kind: EventKind::Impact { outcome, impact, autoland },
+			});
+			if outcome.is_crash() {
+				self.debug.pause_all_systems = true; // stop simulation on crash
+			}
+		}
+	}
+
+	fn update_plane_air(&mut self) {
+		self.plane.air = self.atmosphere.at(self.plane.altitude());
+		self.plane.wind = self.wind.at(self.plane.position(), self.time(), &self.tilemap);
+	}
+
+	/// Simulated time (s).
+	pub fn time(&self) -> f32 {
+		self.frame as f32 * Self::DT
+	}
+
+	/// Plane position and pitch to render: interpolated between the last two physics steps
+	/// according to the time left in the accumulator, so motion looks smooth at any frame rate.
+	pub fn render_pose(&self) -> (vec2f, f32) {
+		let alpha = (self.time_accumulator / Self::DT).clamp(0.0, 1.0);
+		let (prev_pos, prev_pitch) = self.prev_pose;
+		let (pos, pitch) = (self.plane.position(), self.plane.pitch());
+		if !prev_pos.iter().all(|v| v.is_finite()) || (pos - prev_pos).len() > 10.0 {
+			return (pos, pitch); // reset or teleported
+		}
+		(prev_pos + alpha * (pos - prev_pos), prev_pitch + alpha * wrap_angle(pitch - prev_pitch))
+	}
+
+	/// Start over, keeping the key bindings (they are settings, not game state).
+	pub fn reset(&mut self) {
+		let controls = mem::take(&mut self.controls);
+		*self = Self { controls, ..default() };
+	}
+
+	/// Start an automatic landing with the aim point on the terrain at `x` (m).
+	pub fn engage_autoland(&mut self, x: f32) -> Result<()> {
+		let touchdown = vec2(x, self.tilemap.terrain_height(x));
+		self.autoland.engage(touchdown, &mut self.plane, &mut self.autopilot)
+	}
+
+	/// Height of the aircraft's center of mass above the terrain right below it (m).
+	pub fn height_above_ground(&self) -> f32 {
+		let pos = self.plane.position();
+		pos.y() - self.tilemap.surface_height(pos, tilemap_x_offset(pos))
+	}
+
+	/// Gear not down while apparently about to land.
+	pub fn gear_warning(&self) -> bool {
+		let plane = &self.plane;
+		let approach = self.height_above_ground() < 150.0 && plane.body.velocity.y() < -1.0 && plane.engine.throttle < 0.3;
+		plane.gear.is_retractable() && !plane.gear.is_down() && (approach || plane.controls.flaps > 0.5)
+	}
+
 	pub fn record_plot(&mut self) {
-		let t = self.frame as f32 / 1000.0;
+		let t = self.time();
 		let body = &self.plane.body;
+		let elevator = self.plane.surfaces.iter().find(|s| s.control == ControlAxis::Pitch);
 		self.plotter.pushf(|| {
 			vec![
 				t, //_
@@ -89,9 +214,12 @@ impl GameState {
 				body.rot_velocity / DEG,
 				body.rot_accel / DEG,
 				self.plane.wings_aoa() / DEG,
-				self.plane.winglet_lift(&self.plane.wings).len(),
-				self.plane.winglet_induced_drag(&self.plane.wings).len(),
-				(body.acceleration + vec2f(0.0, self.plane.gravity)).len() / self.plane.gravity,
+				self.plane.main_wing().map_or(0.0, |w| self.plane.winglet_lift(w).len()),
+				self.plane.main_wing().map_or(0.0, |w| self.plane.winglet_induced_drag(w).len()),
+				self.plane.load_factor(),
+				self.plane.main_wing().map_or(0.0, |w| 100.0 * self.plane.winglet_ground_effect(w)),
+				elevator.map_or(0.0, |s| s.commanded_deflection(&self.plane.controls) / DEG),
+				elevator.map_or(0.0, |s| s.deflection / DEG),
 			]
 		});
 	}
@@ -102,12 +230,7 @@ impl GameState {
 
 		self.last_frame_micros = (now - last).try_into().unwrap_or(0);
 
-		if self.last_frame_micros < 1 {
-			self.last_frame_micros = 1;
-		}
-		if self.last_frame_micros > 1000 {
-			self.last_frame_micros = 1000;
-		}
+		self.last_frame_micros = self.last_frame_micros.clamp(1, 1_000_000);
 
 		let fps = 1.0 / ((self.last_frame_micros as f32) / 1e6);
 		self.last_fps = 0.95 * self.last_fps + 0.05 * fps;
@@ -126,7 +249,7 @@ impl GameState {
 	fn tick_camera(&mut self) {
 		let a = self.camera_follow_speed.clamp(0.0, 1.0);
 		let b = 1.0 - a;
-		self.camera_follow_buf = a * self.plane.position() + b * self.camera_follow_buf;
+		self.camera_follow_buf = a * self.render_pose().0 + b * self.camera_follow_buf;
 		if self.camera_follows {
 			self.camera.world_position = a * self.camera_follow_buf + b * self.camera.world_position;
 		}
@@ -149,13 +272,25 @@ impl Default for GameState {
 			camera_follow_speed: 0.4,
 			camera_follow_buf: default(),
 			debug: default(),
+			controls: default(),
+			keys_captured: false,
 			last_frame_micro_timestamp: micros_since_epoch(),
 			last_frame_micros: 0,
 			last_fps: 0.0,
 			last_frame_cpu_micros: 0,
 			fps_label: default(),
 			plane: Plane::default(),
+			autopilot: default(),
+			flight_plan: default(),
+			autoland: default(),
+			atmosphere: default(),
+			wind: default(),
+			variometer: default(),
+			crash_detector: default(),
+			events: default(),
 			tilemap: Tilemap::airstrip(vec2(1024, 1024)),
+			time_accumulator: 0.0,
+			prev_pose: default(),
 			plotter: Plotter::new(&[
 				"t (s)", //_
 				"x position (m)",
@@ -171,6 +306,9 @@ impl Default for GameState {
 				"lift (N)",
 				"drag (N)",
 				"G force",
+				"ground effect (%)",
+				"elevator command (deg)",
+				"elevator (deg)",
 			]),
 		}
 	}
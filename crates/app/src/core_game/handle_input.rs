@@ -9,20 +9,33 @@ impl GameState {
 		self.inputs.reset();
 	}
 
+	/// Move the controls bound to held keys, trigger actions for pressed keys.
 	fn handle_keys(&mut self) {
-		let plane = &mut self.plane;
-		let throttle_step = plane.max_propeller_force / 100.0;
-		let elevator_step = 60.0 * DEG / 100.0;
-		for k in &self.inputs.keys_down {
-			use egui::Key;
-			match k {
-				Key::ArrowLeft | Key::S => plane.propeller_force -= throttle_step,
-				Key::ArrowRight | Key::F => plane.propeller_force += throttle_step,
-				Key::ArrowDown | Key::D => plane.elevator.pitch -= elevator_step,
-				Key::ArrowUp | Key::E => plane.elevator.pitch += elevator_step,
-				Key::Space => toggle(&mut self.debug.pause_all_systems),
-				_ => (),
-			};
+		if self.keys_captured {
+			return;
+		}
+		let (controls, inputs, plane) = (&self.controls, &self.inputs, &mut self.plane);
+		// real time, also when paused
+		let dt = (self.last_frame_micros as f32 / 1e6).min(Self::MAX_FRAME_TIME);
+
+		let pitch_input = controls.axis(Axis::Pitch, inputs) != 0.0;
+		// without keys, centering would fight the autopilot
+		if pitch_input || self.autopilot.pitch_mode == PitchMode::Off {
+			controls.move_axis(Axis::Pitch, inputs, dt, -1.0..=1.0, &mut plane.controls.pitch);
+		}
+		let throttle_input = controls.move_axis(Axis::Throttle, inputs, dt, 0.0..=1.0, &mut plane.engine.throttle);
+		controls.move_axis(Axis::PitchTrim, inputs, dt, -1.0..=1.0, &mut plane.controls.pitch_trim);
+		self.autoland.pilot_input(pitch_input, throttle_input, &mut self.autopilot);
+
+		plane.controls.brakes = if controls.triggered(Action::Brakes, inputs) { 1.0 } else { 0.0 };
+		if controls.triggered(Action::Gear, inputs) {
+			toggle(&mut plane.gear.down_selected);
+		}
+		if controls.triggered(Action::ParkingBrake, inputs) {
+			toggle(&mut plane.controls.parking_brake);
+		}
+		if controls.triggered(Action::Pause, inputs) {
+			toggle(&mut self.debug.pause_all_systems);
 		}
 	}
 
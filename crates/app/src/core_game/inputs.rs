@@ -14,6 +14,9 @@ pub struct Inputs {
 
 	#[inspect(hide)]
 	pub keys_down: HashSet<Key>,
+	/// Keys pressed since the last frame.
+	#[inspect(hide)]
+	pub keys_just_pressed: HashSet<Key>,
 }
 
 impl Inputs {
@@ -29,6 +32,7 @@ impl Inputs {
 		self.zoom_delta *= inputs.zoom_delta();
 		self.scroll_delta += vec2(inputs.raw_scroll_delta.x, inputs.raw_scroll_delta.y);
 
+		self.keys_just_pressed.extend(inputs.keys_down.iter().filter(|k| !self.keys_down.contains(k)));
 		self.keys_down.clear();
 		self.keys_down.extend(&inputs.keys_down);
 	}
@@ -39,6 +43,7 @@ impl Inputs {
 		self.zoom_delta = 1.0;
 		self.mouse_just_pressed = false;
 		self.mouse_just_released = false;
+		self.keys_just_pressed.clear();
 	}
 }
 
@@ -52,6 +57,7 @@ impl Default for Inputs {
 			mouse_just_pressed: default(),
 			mouse_just_released: default(),
 			keys_down: default(),
+			keys_just_pressed: default(),
 		}
 	}
 }
@@ -0,0 +1,127 @@
+use crate::prelude::*;
+
+/// Fixed or retractable landing gear.
+/// Wheel positions (gear down) are `Plane::wheels`, this holds the retraction mechanism.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct LandingGear {
+	/// Which of `Plane::wheels` retract. None: fixed gear.
+	pub retracts: [bool; 2],
+	/// Wheel displacement when fully retracted, relative to the down position (m).
+	pub retracted_offset: vec2f,
+	/// Time for a full extension or retraction (s).
+	pub transition_time: f32,
+	/// Parasitic drag with the gear fully down (N/(m/s)²). 0 for fixed gear, whose drag is part of the body's.
+	pub drag: f32,
+	/// The gear does not move above this airspeed (m/s).
+	pub max_operating_speed: f32,
+	/// The gear must not be down above this airspeed (m/s).
+	pub max_extended_speed: f32,
+
+	/// Pilot's gear lever.
+	pub down_selected: bool,
+	/// 0: up and locked, 1: down and locked.
+	pub extension: f32,
+}
+
+impl LandingGear {
+	pub fn is_retractable(&self) -> bool {
+		self.retracts.iter().any(|&r| r)
+	}
+
+	pub fn is_down(&self) -> bool {
+		self.extension >= 1.0
+	}
+
+	pub fn is_up(&self) -> bool {
+		self.extension <= 0.0
+	}
+
+	/// Move the gear towards the selected position, unless the airspeed is too high.
+	/// With weight on the wheels, the gear does not retract.
+	pub fn tick(&mut self, dt: f32, airspeed: f32, weight_on_wheels: bool) {
+		if !self.is_retractable() {
+			self.extension = 1.0;
+			return;
+		}
+		if airspeed > self.max_operating_speed {
+			return;
+		}
+		let target = if self.down_selected || weight_on_wheels { 1.0 } else { 0.0 };
+		let step = dt / self.transition_time.max(dt);
+		self.extension += (target - self.extension).clamp(-step, step);
+	}
+
+	/// Current position of wheel `i`, given its down position.
+	pub fn wheel_pos(&self, i: usize, down_pos: vec2f) -> vec2f {
+		if self.retracts[i] {
+			down_pos + (1.0 - self.extension) * self.retracted_offset
+		} else {
+			down_pos
+		}
+	}
+
+	/// Wheel `i` is stowed and can not touch the terrain.
+	pub fn is_stowed(&self, i: usize) -> bool {
+		self.retracts[i] && self.is_up()
+	}
+
+	/// Current parasitic drag factor (N/(m/s)²).
+	pub fn drag_factor(&self) -> f32 {
+		self.drag * self.extension
+	}
+
+	/// Gear not up above its speed limit.
+	pub fn is_overspeed(&self, airspeed: f32) -> bool {
+		self.is_retractable() && !self.is_up() && airspeed > self.max_extended_speed
+	}
+
+	pub fn status(&self) -> &'static str {
+		match (self.is_down(), self.is_up()) {
+			(true, _) => "DOWN",
+			(_, true) => "UP",
+			_ => "IN TRANSIT",
+		}
+	}
+}
+
+impl Default for LandingGear {
+	fn default() -> Self {
+		Self {
+			retracts: [false, false],
+			retracted_offset: vec2(0.0, 0.9),
+			transition_time: 6.0,
+			drag: 0.0,
+			max_operating_speed: 70.0,
+			max_extended_speed: 80.0,
+			down_selected: true,
+			extension: 1.0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn retract_and_extend() {
+		let mut gear = LandingGear {
+			retracts: [false, true],
+			transition_time: 2.0,
+			drag: 0.05,
+			..default()
+		};
+		gear.down_selected = false;
+		gear.tick(1.0, 100.0, false);
+		assert!(gear.is_down(), "does not move above max_operating_speed");
+		gear.tick(1.0, 50.0, true);
+		assert!(gear.is_down(), "does not retract on the ground");
+		gear.tick(1.0, 50.0, false);
+		assert_eq!(gear.status(), "IN TRANSIT");
+		gear.tick(1.0, 50.0, false);
+		assert!(gear.is_up() && gear.is_stowed(1) && !gear.is_stowed(0));
+		assert_eq!(gear.wheel_pos(1, vec::ZERO), gear.retracted_offset);
+		assert_eq!(gear.drag_factor(), 0.0);
+	}
+}
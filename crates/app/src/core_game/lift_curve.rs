@@ -0,0 +1,122 @@
+use crate::prelude::*;
+
+/// Lift and drag coefficients of a lifting surface as a function of angle of attack.
+///
+/// Below `critical_aoa` the flow is attached: lift grows (almost) linearly with AoA
+/// and drag is dominated by `drag_factor`.
+/// Beyond `critical_aoa` the flow separates: lift suddenly drops to `post_stall_lift`
+/// and fades away, while the surface increasingly behaves like a flat plate (`separated_drag`).
+///
+/// Forces are `coefficient * airspeed²`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect, PartialEq)]
+pub struct LiftCurve {
+	/// Attached-flow drag per |sin(aoa)|.
+	pub drag_factor: f32,
+	/// Attached-flow lift over drag.
+	pub lift_to_drag: f32,
+	/// Angle of attack where the flow separates (rad).
+	pub critical_aoa: f32,
+	/// Fraction of the peak lift that remains right after the stall.
+	pub post_stall_lift: f32,
+	/// Flat-plate drag at 90° AoA, added on top of `drag_factor` once stalled.
+	pub separated_drag: f32,
+}
+
+impl LiftCurve {
+	/// Lift and drag without any stall: lift keeps increasing up to 90° AoA.
+	/// This is the original two-parameter model.
+	pub fn simple(drag_factor: f32, lift_to_drag: f32) -> Self {
+		Self {
+			drag_factor,
+			lift_to_drag,
+			critical_aoa: 90.0 * DEG,
+			post_stall_lift: 0.0,
+			separated_drag: 0.0,
+		}
+	}
+
+	/// Simple model, but stalling at `critical_aoa`.
+	pub fn with_stall(drag_factor: f32, lift_to_drag: f32, critical_aoa: f32) -> Self {
+		Self {
+			critical_aoa,
+			post_stall_lift: 0.3,
+			separated_drag: 4.0 * drag_factor,
+			..Self::simple(drag_factor, lift_to_drag)
+		}
+	}
+
+	/// Signed lift coefficient. Positive lift points 90° counterclockwise from the airflow.
+	pub fn lift(&self, aoa: f32) -> f32 {
+		let a = wrap_angle(aoa).abs();
+		let (sin, cos) = a.sin_cos();
+		let attached = self.lift_to_drag * self.drag_factor * sin * cos;
+
+		let lift = if self.is_stalled(aoa) {
+			let (sin_c, cos_c) = self.critical_aoa.sin_cos();
+			let peak = self.lift_to_drag * self.drag_factor * sin_c * cos_c;
+			let remaining = self.post_stall_lift * peak * (cos / cos_c.max(1e-6)).max(0.0);
+			let flat_plate = self.separated_drag * sin * cos;
+			remaining + flat_plate
+		} else {
+			attached
+		};
+
+		lift * wrap_angle(aoa).signum()
+	}
+
+	/// Drag coefficient (always positive).
+	pub fn drag(&self, aoa: f32) -> f32 {
+		let attached = self.induced_drag(aoa);
+
+		if self.is_stalled(aoa) {
+			let sin = wrap_angle(aoa).sin();
+			let sin_c = self.critical_aoa.sin();
+			attached + self.separated_drag * (sin * sin - sin_c * sin_c).max(0.0)
+		} else {
+			attached
+		}
+	}
+
+	/// Lift-dependent part of the drag coefficient: the attached-flow drag, without the separated-flow drag.
+	/// This is the part that ground effect reduces.
+	pub fn induced_drag(&self, aoa: f32) -> f32 {
+		self.drag_factor * wrap_angle(aoa).abs().sin()
+	}
+
+	pub fn is_stalled(&self, aoa: f32) -> bool {
+		wrap_angle(aoa).abs() > self.critical_aoa
+	}
+}
+
+/// Wrap angle to -PI..PI.
+pub fn wrap_angle(angle: f32) -> f32 {
+	(angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn simple_curve_never_stalls() {
+		let curve = LiftCurve::simple(1.0, 15.0);
+		for aoa in [-80.0, -20.0, 0.0, 5.0, 45.0, 89.0] {
+			let aoa = aoa * DEG;
+			assert!(!curve.is_stalled(aoa));
+			assert!((curve.lift(aoa) - 15.0 * aoa.sin() * aoa.cos()).abs() < 1e-4);
+			assert!((curve.drag(aoa) - aoa.sin().abs()).abs() < 1e-4);
+		}
+	}
+
+	#[test]
+	fn lift_drops_after_stall() {
+		let curve = LiftCurve::with_stall(1.0, 15.0, 15.0 * DEG);
+		let before = curve.lift(14.9 * DEG);
+		let after = curve.lift(15.1 * DEG);
+		assert!(after < 0.75 * before);
+		assert!(curve.drag(30.0 * DEG) > LiftCurve::simple(1.0, 15.0).drag(30.0 * DEG));
+		assert_eq!(curve.induced_drag(10.0 * DEG), curve.drag(10.0 * DEG));
+		assert_eq!(curve.induced_drag(30.0 * DEG), LiftCurve::simple(1.0, 15.0).drag(30.0 * DEG));
+		assert_eq!(curve.lift(-10.0 * DEG), -curve.lift(10.0 * DEG));
+	}
+}
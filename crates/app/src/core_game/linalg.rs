@@ -0,0 +1,109 @@
+use crate::prelude::*;
+use num_complex::Complex64;
+
+/// Solve `a x = b` by Gaussian elimination with partial pivoting.
+/// None if `a` is (numerically) singular.
+pub fn solve_linear<const N: usize>(mut a: [[f32; N]; N], mut b: [f32; N]) -> Option<[f32; N]> {
+	let scale = a.iter().flatten().fold(0.0f32, |max, v| max.max(v.abs()));
+	for col in 0..N {
+		let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+		if a[pivot][col].abs() <= 1e-6 * scale {
+			return None;
+		}
+		a.swap(col, pivot);
+		b.swap(col, pivot);
+		for row in col + 1..N {
+			let f = a[row][col] / a[col][col];
+			let pivot_row = a[col];
+			for (dst, src) in iter::zip(&mut a[row][col..], &pivot_row[col..]) {
+				*dst -= f * src;
+			}
+			b[row] -= f * b[col];
+		}
+	}
+
+	let mut x = [0.0; N];
+	for row in (0..N).rev() {
+		let sum: f32 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+		x[row] = (b[row] - sum) / a[row][row];
+	}
+	Some(x)
+}
+
+/// Eigenvalues of `a`, as the roots of its characteristic polynomial.
+/// Fine for the small matrices of stability analysis, not for large or ill-conditioned ones.
+pub fn eigenvalues<const N: usize>(a: [[f64; N]; N]) -> Vec<Complex64> {
+	// Faddeev-LeVerrier: det(λI - a) = λ^N + c[N-1] λ^(N-1) + ... + c[0]
+	let mut c = vec![0.0; N + 1];
+	c[N] = 1.0;
+	let mut m = [[0.0; N]; N];
+	for k in 1..=N {
+		let mut am = [[0.0; N]; N];
+		for i in 0..N {
+			for j in 0..N {
+				am[i][j] = (0..N).map(|l| a[i][l] * m[l][j]).sum::<f64>() + if i == j { c[N - k + 1] } else { 0.0 };
+			}
+		}
+		m = am;
+		let trace: f64 = (0..N).map(|i| (0..N).map(|l| a[i][l] * m[l][i]).sum::<f64>()).sum();
+		c[N - k] = -trace / k as f64;
+	}
+	polynomial_roots(&c)
+}
+
+/// Complex roots of the monic polynomial `c[0] + c[1] x + ... + x^n` (Durand-Kerner).
+fn polynomial_roots(c: &[f64]) -> Vec<Complex64> {
+	let n = c.len() - 1;
+	let eval = |z: Complex64| c.iter().rev().fold(Complex64::new(0.0, 0.0), |acc, &c| acc * z + c);
+	let radius = 1.0 + c[..n].iter().fold(0.0f64, |max, c| max.max(c.abs()));
+	let mut roots: Vec<Complex64> = (0..n).map(|i| radius * Complex64::new(0.4, 0.9).powu(i as u32 + 1)).collect();
+	for _ in 0..1000 {
+		let mut change = 0.0f64;
+		for i in 0..n {
+			let denominator: Complex64 = (0..n).filter(|&j| j != i).map(|j| roots[i] - roots[j]).product();
+			let delta = eval(roots[i]) / denominator;
+			if delta.is_finite() {
+				roots[i] -= delta;
+				change = change.max(delta.norm() / roots[i].norm().max(1.0));
+			}
+		}
+		if change < 1e-14 {
+			break;
+		}
+	}
+	// real roots come out with a tiny imaginary part
+	for root in &mut roots {
+		if root.im.abs() < 1e-9 * root.norm().max(1.0) {
+			root.im = 0.0;
+		}
+	}
+	roots
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn solves_and_detects_singular() {
+		let x = solve_linear([[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]], [5.0, 3.0, 4.0]).unwrap();
+		for (got, want) in iter::zip(x, [1.0, 2.0, 1.0]) {
+			assert!((got - want).abs() < 1e-5, "{x:?}");
+		}
+		assert_eq!(solve_linear([[1.0, 2.0], [2.0, 4.0]], [1.0, 2.0]), None);
+	}
+
+	#[test]
+	fn eigenvalues_of_oscillator_and_diagonal() {
+		// x'' = -4 x - 0.2 x': λ = -0.1 ± 1.997i
+		let mut eig = eigenvalues([[0.0, 1.0], [-4.0, -0.2]]);
+		eig.sort_by(|a, b| a.im.total_cmp(&b.im));
+		assert!((eig[1] - Complex64::new(-0.1, (4.0f64 - 0.01).sqrt())).norm() < 1e-9, "{eig:?}");
+		assert_eq!(eig[0], eig[1].conj());
+
+		let mut eig = eigenvalues([[3.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 0.5]]);
+		eig.sort_by(|a, b| a.re.total_cmp(&b.re));
+		assert_eq!(eig.iter().map(|z| (z.re * 1e9).round() / 1e9).collect::<Vec<_>>(), [-1.0, 0.5, 3.0]);
+		assert!(eig.iter().all(|z| z.im == 0.0));
+	}
+}
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+/// A mass at a fixed position in the aircraft: airframe, pilot, fuel tank, cargo...
+/// Positions are relative to the airframe datum (the origin of the aircraft's sprite).
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+pub struct MassStation {
+	pub name: String,
+	pub kind: StationKind,
+	/// m
+	pub pos: vec2f,
+	/// kg. For fuel tanks: capacity, the actual load follows the remaining fuel.
+	pub mass: f32,
+	/// Radius of gyration around `pos` (m). Zero for point masses.
+	pub gyration_radius: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StationKind {
+	Airframe,
+	Pilot,
+	FuelTank,
+	Cargo,
+}
+
+/// Total mass, center of gravity and rotational inertia (around the CG).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+	/// kg
+	pub mass: f32,
+	/// Relative to the datum (m).
+	pub cg: vec2f,
+	/// kg m²
+	pub rot_inertia: f32,
+}
+
+impl MassStation {
+	pub fn new(name: &str, kind: StationKind, pos: vec2f, mass: f32) -> Self {
+		Self {
+			name: name.into(),
+			kind,
+			pos,
+			mass,
+			gyration_radius: 0.0,
+		}
+	}
+}
+
+impl MassProperties {
+	/// Combine `(position, mass, radius of gyration)` items.
+	pub fn sum(items: impl Iterator<Item = (vec2f, f32, f32)> + Clone) -> Self {
+		let mass: f32 = items.clone().map(|(_, m, _)| m).sum();
+		if mass <= 0.0 {
+			return Self {
+				mass: 0.0,
+				cg: vec::ZERO,
+				rot_inertia: 0.0,
+			};
+		}
+		let cg = items.clone().map(|(pos, m, _)| m * pos).fold(vec::ZERO, |a, b| a + b) / mass;
+		let rot_inertia = items.map(|(pos, m, k)| m * ((pos - cg).len2() + k * k)).sum();
+		Self { mass, cg, rot_inertia }
+	}
+}
+
+impl EguiInspect for StationKind {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn center_of_gravity_and_inertia() {
+		let props = MassProperties::sum([(vec2(-1.0, 0.0), 100.0, 0.0), (vec2(2.0, 0.0), 50.0, 1.0)].into_iter());
+		assert_eq!(props.mass, 150.0);
+		assert!((props.cg - vec2(0.0, 0.0)).len() < 1e-5);
+		assert!((props.rot_inertia - (100.0 * 1.0 + 50.0 * (4.0 + 1.0))).abs() < 1e-3);
+	}
+}
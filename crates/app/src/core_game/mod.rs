@@ -1,25 +1,65 @@
+mod actuator;
+mod aircraft;
+mod atmosphere;
+mod autoland;
+mod autopilot;
+mod bindings;
+mod crash;
 mod drawing;
+mod events;
+mod flight_plan;
 mod game_state;
 mod handle_input;
 mod inputs;
+mod landing_gear;
+mod lift_curve;
+mod linalg;
+mod mass_balance;
+mod performance;
 mod plane;
 mod plotter;
+mod powerplant;
 mod rigid_body;
 mod settings;
+mod soaring;
+mod stability;
 mod tile;
 mod tilemap;
+mod trim;
+mod tyre;
 mod vec2d;
+mod wind;
 mod winglet;
 
+pub use actuator::*;
+pub use aircraft::*;
+pub use atmosphere::*;
+pub use autoland::*;
+pub use autopilot::*;
+pub use bindings::*;
+pub use crash::*;
 pub use drawing::*;
+pub use events::*;
+pub use flight_plan::*;
 pub use game_state::*;
 pub use handle_input::*;
 pub use inputs::*;
+pub use landing_gear::*;
+pub use lift_curve::*;
+pub use linalg::*;
+pub use mass_balance::*;
+pub use performance::*;
 pub use plane::*;
 pub use plotter::*;
+pub use powerplant::*;
 pub use rigid_body::*;
 pub use settings::*;
+pub use soaring::*;
+pub use stability::*;
 pub use tile::*;
 pub use tilemap::*;
+pub use trim::*;
+pub use tyre::*;
 pub use vec2d::*;
+pub use wind::*;
 pub use winglet::*;
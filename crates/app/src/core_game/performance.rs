@@ -0,0 +1,179 @@
+use crate::prelude::*;
+
+/// Performance of an aircraft at one altitude, in its current configuration (loading, flaps, gear),
+/// found by trimming it over a sweep of airspeeds.
+///
+/// Climb and glide follow from the thrust needed for level flight:
+/// climb rate = excess thrust × airspeed / weight, glide ratio = weight / thrust needed.
+#[derive(Clone, Debug)]
+pub struct Performance {
+	/// m
+	pub altitude: f32,
+	/// Slowest level flight, whether or not the engine can sustain it (m/s, true airspeed).
+	pub stall_speed: f32,
+	/// Fastest level flight at full throttle (m/s). None if level flight is not possible.
+	pub max_level_speed: Option<f32>,
+	/// Vy: airspeed with the best rate of climb (m/s).
+	pub best_climb_speed: f32,
+	/// m/s
+	pub best_climb_rate: f32,
+	/// Airspeed with the best glide ratio, engine off (m/s).
+	pub best_glide_speed: f32,
+	pub best_glide_ratio: f32,
+	/// Altitude where the best climb rate drops to `SERVICE_CEILING_CLIMB` (m).
+	/// None if not computed or above `MAX_ALTITUDE`.
+	pub service_ceiling: Option<f32>,
+	/// One point per trimmed airspeed.
+	pub curve: Vec<PerformancePoint>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerformancePoint {
+	/// m/s, true airspeed
+	pub airspeed: f32,
+	/// For level flight (N).
+	pub thrust_required: f32,
+	/// At full throttle (N).
+	pub thrust_available: f32,
+	/// At full throttle (m/s).
+	pub climb_rate: f32,
+	/// Engine off.
+	pub glide_ratio: f32,
+	/// Engine off (m/s).
+	pub sink_rate: f32,
+}
+
+impl Performance {
+	/// Climb rate (m/s) defining the service ceiling (100 ft/min).
+	pub const SERVICE_CEILING_CLIMB: f32 = 0.5;
+	pub const MAX_ALTITUDE: f32 = 10000.0;
+	/// Column names, for plots and CSV.
+	pub const COLUMNS: [&'static str; 6] = ["airspeed (m/s)", "thrust required (N)", "thrust available (N)", "climb rate (m/s)", "glide ratio", "sink rate (m/s)"];
+
+	/// The curves as CSV, one line per airspeed.
+	pub fn to_csv(&self) -> String {
+		let mut csv = Self::COLUMNS.join(",") + "\n";
+		for p in &self.curve {
+			csv += &p.values().map(|v| v.to_string()).join(",");
+			csv += "\n";
+		}
+		csv
+	}
+
+	/// The curves, to show in the plot panel.
+	pub fn plotter(&self) -> Plotter {
+		let mut plotter = Plotter::new(&Self::COLUMNS);
+		for p in &self.curve {
+			plotter.pushf(|| p.values().into());
+		}
+		plotter
+	}
+}
+
+impl PerformancePoint {
+	/// In the order of `Performance::COLUMNS`.
+	pub fn values(&self) -> [f32; 6] {
+		[self.airspeed, self.thrust_required, self.thrust_available, self.climb_rate, self.glide_ratio, self.sink_rate]
+	}
+}
+
+impl Plane {
+	/// Sweep airspeed at `altitude` (m), and altitude for the service ceiling.
+	pub fn performance(&self, atmosphere: &Atmosphere, altitude: f32) -> Result<Performance> {
+		let mut performance = self.performance_at(atmosphere, altitude, 1.0)?;
+		performance.service_ceiling = self.service_ceiling(atmosphere);
+		Ok(performance)
+	}
+
+	/// Performance at one altitude (without service ceiling), trimming every `speed_step` (m/s).
+	fn performance_at(&self, atmosphere: &Atmosphere, altitude: f32, speed_step: f32) -> Result<Performance> {
+		let mut plane = self.clone();
+		plane.body.position[1] = altitude;
+		plane.ground_level = f32::NEG_INFINITY; // no ground effect
+		plane.air = atmosphere.at(altitude);
+		plane.wind = vec::ZERO;
+		let weight = plane.body.mass * plane.gravity;
+
+		let mut full_throttle = plane.engine.clone();
+		full_throttle.throttle = 1.0;
+		full_throttle.rpm = full_throttle.target_rpm();
+
+		let speeds = (1..).map(|i| i as f32 * speed_step).take_while(|&v| v <= plane.engine.prop_max_speed.max(100.0));
+		let curve: Vec<_> = speeds
+			.filter_map(|airspeed| {
+				let thrust_required = plane.trim_with_thrust(airspeed, 0.0).ok()?.thrust;
+				let thrust_available = full_throttle.thrust(airspeed, &plane.air);
+				let glide_ratio = weight / thrust_required.max(f32::EPSILON);
+				Some(PerformancePoint {
+					airspeed,
+					thrust_required,
+					thrust_available,
+					climb_rate: (thrust_available - thrust_required) * airspeed / weight,
+					glide_ratio,
+					sink_rate: airspeed / glide_ratio,
+				})
+			})
+			.collect();
+
+		let Some(slowest) = curve.first() else { bail!("no level flight possible at {altitude:.0} m") };
+		let best = |key: fn(&PerformancePoint) -> f32| *curve.iter().max_by(|a, b| key(a).total_cmp(&key(b))).unwrap_or(slowest);
+		let best_climb = best(|p| p.climb_rate);
+		let best_glide = best(|p| p.glide_ratio);
+
+		Ok(Performance {
+			altitude,
+			stall_speed: slowest.airspeed,
+			max_level_speed: curve.iter().filter(|p| p.climb_rate >= 0.0).map(|p| p.airspeed).reduce(f32::max),
+			best_climb_speed: best_climb.airspeed,
+			best_climb_rate: best_climb.climb_rate,
+			best_glide_speed: best_glide.airspeed,
+			best_glide_ratio: best_glide.glide_ratio,
+			service_ceiling: None,
+			curve,
+		})
+	}
+
+	/// Altitude where the best climb rate drops to `SERVICE_CEILING_CLIMB`.
+	fn service_ceiling(&self, atmosphere: &Atmosphere) -> Option<f32> {
+		let climb_at = |altitude| self.performance_at(atmosphere, altitude, 2.0).map_or(f32::NEG_INFINITY, |p| p.best_climb_rate) - Performance::SERVICE_CEILING_CLIMB;
+		let (mut below, mut climb_below) = (0.0, climb_at(0.0));
+		if climb_below < 0.0 {
+			return None;
+		}
+		const STEP: f32 = 500.0;
+		while below < Performance::MAX_ALTITUDE {
+			let above = below + STEP;
+			let climb_above = climb_at(above);
+			if climb_above < 0.0 {
+				// interpolate (clamped: the climb rate may be -inf where no level flight is possible)
+				let frac = (climb_below / (climb_below - climb_above)).clamp(0.0, 1.0);
+				return Some(below + frac * STEP);
+			}
+			(below, climb_below) = (above, climb_above);
+		}
+		None
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn trainer_performance() {
+		let plane = Plane::default();
+		let sea_level = plane.performance(&Atmosphere::default(), 0.0).unwrap();
+		let p = &sea_level;
+		assert!(p.stall_speed < p.best_glide_speed && p.best_glide_speed < p.max_level_speed.unwrap(), "{p:#?}");
+		assert!(p.stall_speed < p.best_climb_speed && p.best_climb_speed < p.max_level_speed.unwrap(), "{p:#?}");
+		assert!(p.best_climb_rate > 1.0 && p.best_glide_ratio > 5.0, "{p:#?}");
+		let ceiling = p.service_ceiling.unwrap();
+		assert!(ceiling > 1000.0, "{ceiling}");
+
+		// thinner air: less climb
+		let high = plane.performance_at(&Atmosphere::default(), ceiling, 1.0).unwrap();
+		assert!((high.best_climb_rate - Performance::SERVICE_CEILING_CLIMB).abs() < 0.3, "{high:#?}");
+
+		assert_eq!(p.to_csv().lines().count(), p.curve.len() + 1);
+	}
+}
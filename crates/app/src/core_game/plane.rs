@@ -2,21 +2,53 @@ use crate::prelude::*;
 
 #[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
 pub struct Plane {
+	pub name: String,
+	pub description: String,
+	/// Fuselage sprite and its drawn size (m).
+	pub sprite: Sprite,
+	pub sprite_scale: vec2f,
+
 	pub body: RigidBody,
 
 	//	pub display_position: vec2f,
 	pub gravity: f32,
 
-	pub wings: Winglet,
-	pub elevator: Winglet,
+	/// Lifting surfaces: wings, flaps, tail, ...
+	pub surfaces: Vec<Winglet>,
+	pub controls: ControlInputs,
 	pub wheels: [vec2f; 2],
+	pub tyre: Tyre,
+	pub gear: LandingGear,
+	/// Convex collision hull of the airframe (besides the wheels).
+	pub hull: Vec<HullPoint>,
 
 	pub body_drag: f32,
 
-	pub propeller_force: f32,
-	pub max_propeller_force: f32,
+	/// Airframe, pilot, fuel tanks, cargo. Determine mass, center of gravity and rotational inertia.
+	pub stations: Vec<MassStation>,
+	/// Allowed center of gravity range, x relative to the datum (m).
+	pub cg_forward_limit: f32,
+	pub cg_aft_limit: f32,
+
+	pub engine: Powerplant,
+	/// Where the propeller thrust acts.
+	pub propeller_pos: vec2f,
 	pub forces: RefCell<Vec<Force>>,
+	/// Wheels and hull points touching the terrain during the last tick.
+	#[serde(skip)]
+	#[inspect(hide)]
+	pub contacts: RefCell<Vec<ContactPoint>>,
 	pub draw_forces: bool,
+
+	/// Air around the aircraft, updated every tick from the `Atmosphere`.
+	#[serde(default)]
+	pub air: AirData,
+	/// Wind velocity at the aircraft, updated every tick from the `WindField`.
+	#[serde(default)]
+	pub wind: vec2f,
+	/// Terrain height under the center of mass (m), updated every tick. Surfaces keep theirs relative to this (`Winglet::ground_offset`).
+	#[serde(default)]
+	pub ground_level: f32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
@@ -28,26 +60,65 @@ pub struct Force {
 impl Plane {
 	pub fn default() -> Self {
 		Self {
+			name: "trainer".into(),
+			description: "Single-engine two-seat trainer. Forgiving, flaps for short takeoffs.".into(),
+			sprite: Sprite::PLANE,
+			sprite_scale: vec2(8.0, 4.0),
 			//display_position: default(),
 			body_drag: 0.2,
 			gravity: 9.81,
 			draw_forces: true,
 
-			wings: Winglet {
-				pos: vec2(0.2, 0.5),
-				pitch: 3.0 * DEG,
-				drag_factor: 1.0,
-				lift_to_drag: 15.0,
-			},
-			elevator: Winglet {
-				pos: vec2(-3.7, 0.3),
-				pitch: 0.0,
-				drag_factor: 0.2,
-				lift_to_drag: 15.0,
-			},
+			surfaces: vec![
+				Winglet::new("wing", SurfaceKind::MainWing, vec2(0.2, 0.5), 3.0 * DEG, LiftCurve::with_stall(1.0, 15.0, 15.0 * DEG))
+					.with_span(10.5)
+					.with_size(vec2(2.5, 1.25)),
+				Winglet::new("flaps", SurfaceKind::Flap, vec2(-0.9, 0.45), -4.0 * DEG, LiftCurve::with_stall(0.15, 8.0, 35.0 * DEG))
+					.with_control(ControlAxis::Flaps, 30.0 * DEG)
+					.with_actuator(Actuator { max_rate: 3.0 * DEG, ..default() }) // electric, 10 s to full flaps
+					.with_span(10.5)
+					.with_size(vec2(0.8, 0.4)),
+				Winglet::new("elevator", SurfaceKind::HorizontalTail, vec2(-3.7, 0.3), 0.0, LiftCurve::with_stall(0.2, 15.0, 15.0 * DEG))
+					.with_control(ControlAxis::Pitch, 30.0 * DEG)
+					.with_actuator(Actuator {
+						limits: vec2(-28.0 * DEG, 23.0 * DEG),
+						max_rate: 60.0 * DEG,
+						time_constant: 0.03,
+						blowback_speed: 75.0,
+					})
+					.with_span(3.4)
+					.with_size(vec2(1.75, 0.75)),
+			],
+			controls: default(),
 			wheels: [vec2(-2.5, -0.75), vec2(1.0, -1.40)],
-			propeller_force: 0.0,
-			max_propeller_force: 2000.0,
+			tyre: default(),
+			gear: default(),
+			hull: [
+				(vec2(3.9, -0.8), HullPart::Propeller),
+				(vec2(3.9, -0.6), HullPart::Fuselage),
+				(vec2(3.6, -0.1), HullPart::Fuselage),
+				(vec2(2.8, 0.1), HullPart::Fuselage),
+				(vec2(-3.1, 1.2), HullPart::Tail),
+				(vec2(-4.0, 1.2), HullPart::Tail),
+				(vec2(-3.7, 0.1), HullPart::Tail),
+				(vec2(0.0, -0.7), HullPart::Fuselage),
+				(vec2(3.0, -0.7), HullPart::Fuselage),
+			]
+			.map(|(pos, part)| HullPoint { pos, part })
+			.into(),
+			stations: vec![
+				MassStation {
+					gyration_radius: 1.54,
+					..MassStation::new("airframe", StationKind::Airframe, vec2(-0.073, -0.08), 820.0)
+				},
+				MassStation::new("pilot", StationKind::Pilot, vec2(0.5, 0.2), 80.0),
+				MassStation::new("fuel", StationKind::FuelTank, vec2(0.2, 0.5), 100.0),
+				MassStation::new("cargo", StationKind::Cargo, vec2(-1.0, 0.0), 0.0),
+			],
+			cg_forward_limit: 0.6,
+			cg_aft_limit: -0.25,
+			engine: default(),
+			propeller_pos: vec2(2.0, 0.0),
 
 			body: RigidBody {
 				position: vec2(8.0, 6.5),
@@ -57,182 +128,348 @@ impl Plane {
 			},
 
 			forces: default(),
+			contacts: default(),
+			air: default(),
+			wind: default(),
+			ground_level: 0.0,
 		}
 	}
 
-	const CENTER_OF_MASS: vec2f = vec2::ZERO;
-
 	pub fn weight(&self) -> f32 {
 		self.body.mass * self.gravity
 	}
 
+	/// Felt acceleration in units of g.
+	pub fn load_factor(&self) -> f32 {
+		(self.body.acceleration + vec2(0.0, self.gravity)).len() / self.gravity
+	}
+
 	pub fn base_speed(&self) -> f32 {
 		// drag  == max_force
 		// v^2 * base_drag == max_force
 		// v == sqrt(max_force / base_drag)
-		(self.max_propeller_force / self.base_drag()).sqrt()
+		(self.engine.static_thrust / self.base_drag()).sqrt()
 	}
 
 	pub fn base_drag(&self) -> f32 {
-		self.body_drag + self.wings.drag_factor + self.elevator.drag_factor
+		self.body_drag + self.surfaces.iter().map(|s| s.curve.drag_factor).sum::<f32>()
 	}
 
+	/// The first surface of kind `MainWing`, if any.
+	pub fn main_wing(&self) -> Option<&Winglet> {
+		self.surfaces.iter().find(|s| s.kind == SurfaceKind::MainWing)
+	}
+
+	/// Any wheel touching the terrain during the last tick.
+	pub fn weight_on_wheels(&self) -> bool {
+		self.contacts.borrow().iter().any(|c| c.part == HullPart::Wheel)
+	}
+
+	/// Angle of attack of the main wing (0 if there is none).
 	pub fn wings_aoa(&self) -> f32 {
-		self.winglet_aoa(&self.wings)
+		self.main_wing().map_or(0.0, |w| self.winglet_aoa(w))
 	}
 
-	pub fn elevator_aoa(&self) -> f32 {
-		self.winglet_aoa(&self.elevator)
+	pub fn tick(&mut self, dt: f32, tilemap: &Tilemap, integrator: Integrator) {
+		self.engine.tick(dt, &self.air);
+		self.gear.tick(dt, self.indicated_airspeed(), self.weight_on_wheels());
+		self.update_mass_properties();
+		self.ground_level = tilemap.surface_height(self.body.position, tilemap_x_offset(self.body.position));
+		let airspeed = self.indicated_airspeed();
+		for surface in &mut self.surfaces {
+			let pos = self.body.transform_rel_pos(surface.pos);
+			surface.ground_offset = tilemap.surface_height(pos, tilemap_x_offset(pos)) - self.ground_level;
+			surface.tick_actuator(dt, &self.controls, airspeed);
+		}
+
+		let mut body = self.body.clone();
+		body.integrate(integrator, dt, |state| self.accelerations(state, tilemap));
+		self.body = body;
 	}
 
-	pub fn tick(&mut self, dt: f32, tilemap: &Tilemap) {
+	/// Linear and angular acceleration if the body were in `state`.
+	/// Leaves `forces` and `contacts` as evaluated for that state.
+	fn accelerations(&mut self, state: &RigidBody, tilemap: &Tilemap) -> (vec2f, f32) {
+		self.body = state.clone();
 		self.update_forces(tilemap);
-		self.body.update_position(dt / 2.0);
-		self.body.update_velocity(dt);
-		self.body.update_position(dt / 2.0);
-
-		self.body.rot_velocity *= 0.999; // some damping
+		(self.body.acceleration, self.body.rot_accel)
 	}
 
 	pub fn update_forces(&mut self, tilemap: &Tilemap) {
 		let forces = &mut self.forces.borrow_mut();
 		forces.clear();
+		let contacts = &mut self.contacts.borrow_mut();
+		contacts.clear();
 
 		// weight
 		forces.push(Force {
-			rel_pos: Self::CENTER_OF_MASS,
+			rel_pos: self.body.center_of_mass,
 			vector: (-self.gravity * self.body.mass) * vec2::EY,
 		});
 
 		// propeller
 		forces.push(Force {
-			rel_pos: vec2(2.0, 0.0),
-			vector: self.propeller_force * (self.body.rotation_matrix() * vec2::EX),
+			rel_pos: self.propeller_pos,
+			vector: self.propeller_thrust() * (self.body.rotation_matrix() * vec2::EX),
 		});
 
-		//wheels
-		for &rel_pos in &self.wheels {
+		//wheels: suspension now, tyre forces once all other forces are known
+		let x_offset = tilemap_x_offset(self.body.position);
+		let mut rolling_wheels = vec![];
+		for (i, &down_pos) in self.wheels.iter().enumerate() {
+			if self.gear.is_stowed(i) {
+				continue;
+			}
+			let rel_pos = self.gear.wheel_pos(i, down_pos);
 			let abs_pos = self.body.transform_rel_pos(rel_pos);
-			if !can_walk(abs_pos) {
-				let mut probe = abs_pos;
-				let mut depth = 0.0;
-				let step = 0.01;
-				while !can_walk(probe) && depth < 2.0 {
-					probe[1] += step;
-					depth += step;
-				}
-
+			if let Some(Contact { depth, normal, surface }) = tilemap.contact(abs_pos, x_offset) {
+				let tangent = -rot90(normal);
 				let v_wheel = self.body.velocity_of_rel_pos(rel_pos);
 				let spring_k = 30.0;
-				let damping = vec2(0.05, 100.0); // wheel damping: small wheel friction (x) + heavy vertical damping (y)
-				let force = vec2(0.0, spring_k * self.body.mass * depth) - v_wheel * damping;
+				let damping = 4.0 * self.body.mass; // shock absorber, about half critical
+				let (v_tangent, v_normal) = (v_wheel.dot(tangent), v_wheel.dot(normal));
+				let normal_force = (spring_k * self.body.mass * depth - damping * v_normal).max(0.0);
+
+				forces.push(Force {
+					rel_pos,
+					vector: normal_force * normal,
+				});
+				rolling_wheels.push(RollingWheel {
+					index: i,
+					rel_pos,
+					tangent,
+					friction: surface.friction(),
+					normal_force,
+					v_tangent,
+					contact: contacts.len(),
+				});
+				contacts.push(ContactPoint {
+					part: HullPart::Wheel,
+					sink_rate: -v_normal,
+					skidding: false,
+				});
+			}
+		}
 
-				forces.push(Force { rel_pos, vector: force });
+		// airframe scraping over the terrain
+		for &HullPoint { pos: rel_pos, part } in &self.hull {
+			let abs_pos = self.body.transform_rel_pos(rel_pos);
+			if let Some(Contact { depth, normal, .. }) = tilemap.contact(abs_pos, x_offset) {
+				let tangent = -rot90(normal);
+				let v = self.body.velocity_of_rel_pos(rel_pos);
+				let (v_tangent, v_normal) = (v.dot(tangent), v.dot(normal));
+				let (spring_k, damping, friction) = (100.0, 10.0, 0.5); // stiffer and more damped than the wheels
+				let normal_force = (self.body.mass * (spring_k * depth - damping * v_normal)).max(0.0);
+				let friction_force = -friction * normal_force * (v_tangent / 0.1).clamp(-1.0, 1.0);
+
+				forces.push(Force {
+					rel_pos,
+					vector: normal_force * normal + friction_force * tangent,
+				});
+				contacts.push(ContactPoint {
+					part,
+					sink_rate: -v_normal,
+					skidding: false,
+				});
 			}
 		}
 
-		// wings
-		for winglet in [self.wings, self.elevator] {
+		// wings, tail, flaps, ...
+		for winglet in &self.surfaces {
 			forces.push(Force {
 				rel_pos: winglet.pos,
-				vector: self.winglet_force(&winglet),
+				vector: self.winglet_force(winglet),
 			})
 		}
 
+		// landing gear drag
+		let gear_pos = (self.wheels[0] + self.wheels[1]) / 2.0;
+		let v_gear = self.body.velocity_of_rel_pos(gear_pos) - self.wind;
+		forces.push(Force {
+			rel_pos: gear_pos,
+			vector: -self.gear.drag_factor() * self.air.density_ratio() * v_gear.len() * v_gear,
+		});
+
 		// body drag
 		forces.push(Force {
-			rel_pos: Self::CENTER_OF_MASS,
+			rel_pos: vec::ZERO,
 			vector: self.winglet_force(&Winglet {
 				pos: vec::ZERO,
 				pitch: 0.0,
-				drag_factor: self.body_drag,
-				lift_to_drag: 0.0,
+				curve: LiftCurve::simple(self.body_drag, 0.0),
+				..default()
 			}),
 		});
 
-		let (total_force, total_torque) = Self::add_forces(forces);
-		self.body.update_accel(total_force, total_torque);
+		// tyres: wheels at rest hold against the other forces, in proportion to how much they can hold
+		let brake = self.controls.brake_input();
+		let holding = |w: &RollingWheel| self.tyre.holding_force(&w.friction, w.normal_force, brake, self.tyre.braked[w.index]);
+		let total_holding: f32 = rolling_wheels.iter().map(holding).sum();
+		let applied = forces.iter().fold(vec::ZERO, |sum, f| sum + f.vector);
+		for w in &rolling_wheels {
+			let share = holding(w) / total_holding.max(f32::EPSILON) * applied.dot(w.tangent);
+			let tyre = self.tyre.force(&w.friction, w.normal_force, w.v_tangent, brake, self.tyre.braked[w.index], share);
+			forces.push(Force {
+				rel_pos: w.rel_pos,
+				vector: tyre.force * w.tangent,
+			});
+			contacts[w.contact].skidding = tyre.skidding;
+		}
+
+		let (total_force, total_torque) = self.add_forces(forces);
+		let pitching_moment: f32 = self.surfaces.iter().map(|s| self.winglet_pitching_moment(s)).sum();
+		self.body.update_accel(total_force, total_torque + pitching_moment);
 	}
 
-	pub fn winglet_force(&self, winglet: &Winglet) -> vec2f {
-		self.winglet_induced_drag(winglet) + self.winglet_lift(winglet)
+	/// `update_forces` without any terrain contact, for analysis of states that may be near or below the ground.
+	/// Height above ground (for ground effect) is kept.
+	pub fn update_forces_airborne(&mut self) {
+		const UP: f32 = 1e5;
+		self.body.position[1] += UP;
+		self.ground_level += UP;
+		self.update_forces(&Tilemap::new(vec2(1, 1), Tile::AIR));
+		self.body.position[1] -= UP;
+		self.ground_level -= UP;
 	}
 
-	pub fn winglet_lift(&self, winglet: &Winglet) -> vec2f {
-		let v = self.body.velocity.normalized();
-		let aoa = self.winglet_aoa(winglet);
+	pub fn propeller_thrust(&self) -> f32 {
+		let forward = self.body.rotation_matrix() * vec2::EX;
+		self.engine.thrust(self.air_velocity().dot(forward), &self.air)
+	}
+
+	/// Fill up the tanks.
+	pub fn refuel(&mut self) {
+		self.engine.fuel = self.fuel_capacity() as f64;
+	}
+
+	/// Total capacity of all fuel tanks (kg).
+	pub fn fuel_capacity(&self) -> f32 {
+		self.stations.iter().filter(|s| s.kind == StationKind::FuelTank).map(|s| s.mass).sum()
+	}
 
-		if aoa.abs() > 90.0 * DEG {
-			return vec::ZERO;
+	/// Actual mass at a station: fuel tanks are filled proportionally to the remaining fuel.
+	pub fn station_load(&self, station: &MassStation) -> f32 {
+		match station.kind {
+			StationKind::FuelTank => station.mass * (self.engine.fuel as f32 / self.fuel_capacity().max(f32::EPSILON)).min(1.0),
+			_ => station.mass,
 		}
+	}
 
-		let general_direction = mat2x2::rotation(aoa) * v;
-		let rota = rot90(self.body.velocity.normalized());
-		let rotb = -rot90(self.body.velocity.normalized());
+	/// Total mass, center of gravity and rotational inertia for the current loading.
+	pub fn mass_properties(&self) -> MassProperties {
+		MassProperties::sum(self.stations.iter().map(|s| (s.pos, self.station_load(s), s.gyration_radius)))
+	}
 
-		let lift_dir = if general_direction.dot(rota) > general_direction.dot(rotb) { rota } else { rotb };
+	/// Is the center of gravity within the allowed range?
+	pub fn cg_in_limits(&self) -> bool {
+		(self.cg_aft_limit..=self.cg_forward_limit).contains(&self.mass_properties().cg.x())
+	}
 
-		//let lift_dir = if aoa > 0.0 { up } else { -up };
+	pub fn update_mass_properties(&mut self) {
+		let props = self.mass_properties();
+		if props.mass > 0.0 {
+			self.body.mass = props.mass;
+			self.body.rot_inertia = props.rot_inertia.max(1.0);
+			self.body.set_center_of_mass(props.cg);
+		}
+	}
 
-		lift_dir //_
-		* winglet.lift_to_drag //_
-		* Self::flow_factor(aoa)//_
-		* self.winglet_induced_drag(winglet).len()
+	pub fn winglet_force(&self, winglet: &Winglet) -> vec2f {
+		self.winglet_induced_drag(winglet) + self.winglet_lift(winglet)
 	}
 
-	fn flow_factor(aoa: f32) -> f32 {
-		f32::cos(aoa).max(0.0)
+	pub fn winglet_lift(&self, winglet: &Winglet) -> vec2f {
+		let v = self.winglet_air_velocity(winglet);
+		rot90(v.normalized()) //_
+		* winglet.curve.lift(self.winglet_aoa(winglet)) //_
+		* self.air.density_ratio()
+		* v.len2()
+		* (1.0 + Winglet::GROUND_EFFECT_LIFT * self.winglet_ground_effect(winglet))
 	}
 
 	pub fn winglet_induced_drag(&self, winglet: &Winglet) -> vec2f {
-		winglet.drag_factor //_
-		* self.winglet_aoa(winglet).sin().abs() //_
-		* self.body.velocity.len2() //_
-		* (-self.body.velocity.normalized()) //_
+		let v = self.winglet_air_velocity(winglet);
+		let aoa = self.winglet_aoa(winglet);
+		(winglet.curve.drag(aoa) - self.winglet_ground_effect(winglet) * winglet.curve.induced_drag(aoa)) //_
+		* self.air.density_ratio()
+		* v.len2() //_
+		* (-v.normalized()) //_
+	}
+
+	/// Ground effect on a winglet, from its height above the terrain (see `Winglet::ground_effect`).
+	pub fn winglet_ground_effect(&self, winglet: &Winglet) -> f32 {
+		winglet.ground_effect(self.body.transform_rel_pos(winglet.pos).y() - self.ground_level - winglet.ground_offset)
+	}
+
+	/// Zero-lift pitching moment (N m, positive: nose up).
+	pub fn winglet_pitching_moment(&self, winglet: &Winglet) -> f32 {
+		winglet.pitching_moment * self.air.density_ratio() * self.winglet_air_velocity(winglet).len2()
+	}
+
+	/// Airflow seen by a winglet: includes the extra velocity due to pitch rotation,
+	/// so e.g. the tail meets the air at a higher angle of attack when the nose pitches up.
+	/// This is what damps pitch oscillations.
+	pub fn winglet_air_velocity(&self, winglet: &Winglet) -> vec2f {
+		self.body.velocity_of_rel_pos(winglet.pos) - self.wind
+	}
+
+	pub fn winglet_is_stalled(&self, winglet: &Winglet) -> bool {
+		winglet.curve.is_stalled(self.winglet_aoa(winglet))
 	}
 
 	fn drag(&self, drag_tensor: vec2f, tensor_rotation: f32) -> vec2f {
-		let v_relative = mat2x2::rotation(-tensor_rotation) * self.body.velocity;
-		let magnitude = drag_tensor.dot(v_relative * v_relative);
-		magnitude * (-self.body.velocity.normalized())
+		let v_relative = mat2x2::rotation(-tensor_rotation) * self.air_velocity();
+		let magnitude = self.air.density_ratio() * drag_tensor.dot(v_relative * v_relative);
+		magnitude * (-self.air_velocity().normalized())
 	}
 
 	pub fn winglet_aoa(&self, winglet: &Winglet) -> f32 {
-		let aoa = if self.body.velocity.len2() < 1.0 {
+		let v = self.winglet_air_velocity(winglet);
+		let aoa = if v.len2() < 1.0 {
 			0.0 // no noise when velocity is ~zero
 		} else {
-			self.body_aoa() + winglet.pitch
+			self.body.rotation + winglet.total_pitch() - f32::atan2(v.y(), v.x())
 		};
 
 		check(aoa);
 		aoa
 	}
 
-	pub fn body_aoa(&self) -> f32 {
-		//let speed = self.body.velocity.normalized();
-		//let direction = self.body.transform_vector(vec2(1.0, 0.0));
-		//speed.dot(direction).acos() * speed.cross(direction).signum()
-
-		let v = self.body.velocity;
-		let theta_speed = f32::atan2(v.y(), v.x());
-		let direction = self.body.rotation;
-		direction - theta_speed
-	}
-
 	pub fn body_drag(&self) -> vec2f {
 		self.drag(vec2(0.0, self.body_drag), self.body.rotation)
 	}
 
-	fn add_forces(forces: &[Force]) -> (vec2f, f32) {
+	/// Total force and torque around the center of mass.
+	fn add_forces(&self, forces: &[Force]) -> (vec2f, f32) {
 		let mut total_force = vec2::ZERO;
 		let mut total_torque = 0.0;
 		for force in forces.iter() {
 			total_force += force.vector;
-			total_torque += force.rel_pos.cross(force.vector);
+			total_torque += self.body.torque(force.rel_pos, force.vector);
 		}
 		(total_force, total_torque)
 	}
 
+	/// Velocity relative to the surrounding air (m/s).
+	pub fn air_velocity(&self) -> vec2f {
+		self.body.velocity - self.wind
+	}
+
+	/// True airspeed (m/s).
+	pub fn true_airspeed(&self) -> f32 {
+		self.air_velocity().len()
+	}
+
+	/// Indicated airspeed (m/s): what the airspeed indicator would show.
+	pub fn indicated_airspeed(&self) -> f32 {
+		self.air.indicated_airspeed(self.true_airspeed())
+	}
+
+	/// Altitude above sea level (m).
+	pub fn altitude(&self) -> f32 {
+		self.body.position.y()
+	}
+
 	pub fn position(&self) -> vec2f {
 		self.body.position
 	}
@@ -243,43 +480,37 @@ impl Plane {
 
 	pub fn draw(&self, sg: &mut Scenegraph) {
 		// fuselage
-		sg.push(QuadInstanceData::new(self.position(), Sprite::PLANE).with(|d| {
-			d.scale = vec2(8.0, 4.0);
+		sg.push(QuadInstanceData::new(self.body.transform_rel_pos(vec::ZERO), self.sprite).with(|d| {
+			d.scale = self.sprite_scale;
 			d.rotation = self.pitch();
 		}));
 
-		// wings
-		{
-			let (pos, rot) = self.body.transform_frame((self.wings.pos, self.wings.pitch));
+		// wings, tail, flaps, ...
+		for winglet in &self.surfaces {
+			let (pos, rot) = self.body.transform_frame((winglet.pos, winglet.total_pitch()));
 			sg.push(QuadInstanceData::new(pos, Sprite::WING).with(|d| {
-				d.scale = vec2(2.5, 1.25);
+				d.scale = winglet.size;
 				d.rotation = rot;
 				d.position[2] = 0.1;
 			}));
 		}
 
-		// elevator
-		{
-			let (pos, rot) = self.body.transform_frame((self.elevator.pos, self.elevator.pitch));
-			sg.push(QuadInstanceData::new(pos, Sprite::WING).with(|d| {
-				d.scale = vec2(1.75, 0.75);
-				d.rotation = rot;
-				d.position[2] = 0.1;
-			}));
-		}
-
-		//wheels
+		//wheels, shrinking into the wheel well while retracting
 		for i in 0..2 {
-			let (pos, rot) = self.body.transform_frame((self.wheels[i], 0.0));
+			if self.gear.is_stowed(i) {
+				continue;
+			}
+			let (pos, rot) = self.body.transform_frame((self.gear.wheel_pos(i, self.wheels[i]), 0.0));
+			let size = if self.gear.retracts[i] { 0.7 * (0.5 + 0.5 * self.gear.extension) } else { 0.7 };
 			sg.push(QuadInstanceData::new(pos, Sprite::WHEEL).with(|d| {
-				d.scale = vec2(0.7, 0.7);
+				d.scale = vec2(size, size);
 				d.rotation = rot;
 				d.position[2] = 0.1;
 			}));
 		}
 
-		// center of mas
-		let (pos, rot) = self.body.transform_frame((vec2(0.0, 0.0), 0.0));
+		// center of mass
+		let (pos, rot) = self.body.transform_frame((self.body.center_of_mass, 0.0));
 		sg.push(QuadInstanceData::new(pos, Sprite::CENTER).with(|d| {
 			d.scale = vec2(0.5, 0.5);
 			d.rotation = rot;
@@ -288,7 +519,7 @@ impl Plane {
 
 		if self.draw_forces {
 			self.draw_forces(sg);
-			self.draw_relative_arrow(sg, vec2::ZERO, self.body.velocity / 10.0, BLUE)
+			self.draw_relative_arrow(sg, self.body.center_of_mass, self.body.velocity / 10.0, BLUE)
 		}
 	}
 
@@ -325,3 +556,17 @@ fn check(v: f32) {
 		panic!("lambda the infinite: {v}");
 	}
 }
+
+/// A wheel on the ground, its tyre force still to be found.
+struct RollingWheel {
+	/// Into `Plane::wheels`.
+	index: usize,
+	rel_pos: vec2f,
+	/// Along the surface.
+	tangent: vec2f,
+	friction: SurfaceFriction,
+	normal_force: f32,
+	v_tangent: f32,
+	/// Into `Plane::contacts`.
+	contact: usize,
+}
@@ -0,0 +1,165 @@
+use crate::prelude::*;
+
+/// Piston engine driving a fixed-pitch propeller.
+///
+/// Throttle sets the target RPM, power follows the RPM (propeller load ~ RPM³)
+/// and falls off with air density. The propeller converts power into thrust,
+/// with an efficiency that drops at high airspeed and a thrust limit at standstill.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Powerplant {
+	/// Pilot throttle position 0..1.
+	pub throttle: f32,
+	pub rpm: f32,
+	pub idle_rpm: f32,
+	pub max_rpm: f32,
+	/// Time constant for RPM changes (s).
+	pub spool_time: f32,
+
+	/// Shaft power at full throttle, sea level (W).
+	pub max_power: f32,
+	/// Thrust at full power and zero airspeed (N).
+	pub static_thrust: f32,
+	/// Peak propeller efficiency 0..1.
+	pub prop_efficiency: f32,
+	/// Airspeed where the propeller stops producing thrust (m/s).
+	pub prop_max_speed: f32,
+
+	/// kg. f64: the fuel burned per tick is below f32 resolution.
+	pub fuel: f64,
+	/// Brake specific fuel consumption (kg/J).
+	pub fuel_consumption: f32,
+
+	/// Engine failure, independent of fuel.
+	pub failed: bool,
+}
+
+impl Powerplant {
+	pub fn is_running(&self) -> bool {
+		!self.failed && self.fuel > 0.0
+	}
+
+	/// Advance RPM and burn fuel.
+	pub fn tick(&mut self, dt: f32, air: &AirData) {
+		self.throttle = self.throttle.clamp(0.0, 1.0);
+
+		let alpha = (dt / self.spool_time.max(dt)).min(1.0);
+		self.rpm += alpha * (self.target_rpm() - self.rpm);
+
+		self.fuel = (self.fuel - (self.fuel_consumption * self.power(air) * dt) as f64).max(0.0);
+	}
+
+	/// RPM the engine settles at for the current throttle.
+	pub fn target_rpm(&self) -> f32 {
+		if self.is_running() {
+			(self.max_rpm * self.throttle.clamp(0.0, 1.0).cbrt()).max(self.idle_rpm)
+		} else {
+			0.0
+		}
+	}
+
+	/// Shaft power (W) delivered at the current RPM.
+	pub fn power(&self, air: &AirData) -> f32 {
+		if !self.is_running() {
+			return 0.0;
+		}
+		let load = (self.rpm / self.max_rpm).clamp(0.0, 1.0).powi(3);
+		// Gagg-Ferrar: normally aspirated engines lose power with density
+		let lapse = (1.132 * air.density_ratio() - 0.132).max(0.0);
+		self.max_power * lapse * load
+	}
+
+	pub fn prop_efficiency(&self, airspeed: f32) -> f32 {
+		let v = airspeed / self.prop_max_speed;
+		(self.prop_efficiency * (1.0 - v * v)).max(0.0)
+	}
+
+	/// Propeller thrust (N) at forward `airspeed` (m/s).
+	pub fn thrust(&self, airspeed: f32, air: &AirData) -> f32 {
+		let power = self.power(air);
+		let static_thrust = self.static_thrust * power / self.max_power.max(1.0);
+		if airspeed <= 0.0 {
+			return static_thrust;
+		}
+		(self.prop_efficiency(airspeed) * power / airspeed).min(static_thrust)
+	}
+
+	/// Fuel flow (kg/h) at the current power setting.
+	pub fn fuel_flow(&self, air: &AirData) -> f32 {
+		3600.0 * self.fuel_consumption * self.power(air)
+	}
+}
+
+impl Default for Powerplant {
+	fn default() -> Self {
+		Self {
+			throttle: 0.0,
+			rpm: 0.0,
+			idle_rpm: 700.0,
+			max_rpm: 2700.0,
+			spool_time: 0.5,
+			max_power: 150e3,
+			static_thrust: 2000.0,
+			prop_efficiency: 0.8,
+			prop_max_speed: 150.0,
+			fuel: 100.0,
+			fuel_consumption: 0.3 / 3.6e6, // 0.3 kg/kWh
+			failed: false,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn full_power() -> Powerplant {
+		let mut engine = Powerplant { throttle: 1.0, ..default() };
+		engine.rpm = engine.target_rpm();
+		engine
+	}
+
+	#[test]
+	fn thrust_lapse() {
+		let engine = full_power();
+		let isa = Atmosphere::default();
+		let sea_level = isa.at(0.0);
+		assert!((engine.thrust(0.0, &sea_level) - engine.static_thrust).abs() < 1.0);
+		// less thrust with airspeed, none at the propeller's max speed
+		let (slow, fast) = (engine.thrust(30.0, &sea_level), engine.thrust(90.0, &sea_level));
+		assert!(slow > fast && fast > 0.0, "{slow} {fast}");
+		assert_eq!(engine.thrust(engine.prop_max_speed, &sea_level), 0.0);
+		// and with altitude
+		let high = isa.at(3000.0);
+		assert!(engine.power(&high) < 0.75 * engine.power(&sea_level));
+		assert!(engine.thrust(90.0, &high) < fast);
+	}
+
+	#[test]
+	fn fuel_burn() {
+		let mut engine = full_power();
+		let air = Atmosphere::default().at(0.0);
+		// 150 kW at 0.3 kg/kWh
+		assert!((engine.fuel_flow(&air) - 45.0).abs() < 1e-3);
+		let fuel = engine.fuel;
+		for _ in 0..1000 {
+			engine.tick(0.01, &air);
+		}
+		let burned = (fuel - engine.fuel) as f32;
+		assert!((burned - 45.0 * 10.0 / 3600.0).abs() < 1e-3, "{burned} kg");
+	}
+
+	#[test]
+	fn stops_when_tanks_are_empty() {
+		let mut engine = full_power();
+		engine.fuel = 1e-3;
+		let air = Atmosphere::default().at(0.0);
+		for _ in 0..1000 {
+			engine.tick(0.01, &air);
+		}
+		assert_eq!(engine.fuel, 0.0);
+		assert!(!engine.is_running());
+		assert_eq!(engine.thrust(0.0, &air), 0.0);
+		assert!(engine.rpm < 1.0, "{}", engine.rpm);
+	}
+}
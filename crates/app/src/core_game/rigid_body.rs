@@ -3,6 +3,7 @@ use crate::prelude::*;
 #[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
 pub struct RigidBody {
 	pub mass: f32,
+	/// Position of the center of mass.
 	pub position: vec2f,
 	pub velocity: vec2f,
 	pub acceleration: vec2f,
@@ -11,6 +12,40 @@ pub struct RigidBody {
 	pub rotation: f32,
 	pub rot_velocity: f32,
 	pub rot_accel: f32,
+
+	/// Center of mass, relative to the body's reference point (datum).
+	/// All `rel_pos` arguments are relative to the datum.
+	#[serde(default)]
+	pub center_of_mass: vec2f,
+}
+
+/// Numerical scheme for `RigidBody::integrate`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Integrator {
+	/// First order: velocity first, then position with the new velocity. 1 force evaluation.
+	SymplecticEuler,
+	/// Half position step, full velocity step, half position step. 1 force evaluation.
+	#[default]
+	Midpoint,
+	/// Classic 4th order Runge-Kutta. 4 force evaluations.
+	RK4,
+}
+
+/// Time derivative of the state: velocity, rot. velocity, acceleration, rot. acceleration.
+type Derivative = (vec2f, f32, vec2f, f32);
+
+impl Integrator {
+	pub const ALL: [Self; 3] = [Self::SymplecticEuler, Self::Midpoint, Self::RK4];
+}
+
+impl EguiInspect for Integrator {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+
+	fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+		inspect_enum_mut(self, &Self::ALL, label, ui)
+	}
 }
 
 impl Default for RigidBody {
@@ -24,6 +59,7 @@ impl Default for RigidBody {
 			rotation: default(),
 			rot_velocity: default(),
 			rot_accel: default(),
+			center_of_mass: default(),
 		}
 	}
 }
@@ -42,6 +78,10 @@ impl RigidBody {
 	pub fn update_position(&mut self, dt: f32) {
 		self.position += dt * self.velocity;
 		self.rotation += dt * self.rot_velocity;
+		self.wrap_rotation();
+	}
+
+	fn wrap_rotation(&mut self) {
 		if self.rotation > PI {
 			self.rotation -= 2.0 * PI;
 		} else if self.rotation < -PI {
@@ -49,6 +89,49 @@ impl RigidBody {
 		}
 	}
 
+	/// Advance by `dt` using `integrator`.
+	/// `accel` returns the linear and angular acceleration for a given state of the body,
+	/// it may be called several times per step (e.g. 4x for RK4).
+	/// `acceleration` and `rot_accel` are left at their values for the initial state.
+	pub fn integrate(&mut self, integrator: Integrator, dt: f32, mut accel: impl FnMut(&RigidBody) -> (vec2f, f32)) {
+		(self.acceleration, self.rot_accel) = accel(self);
+		match integrator {
+			Integrator::SymplecticEuler => {
+				self.update_velocity(dt);
+				self.update_position(dt);
+			}
+			Integrator::Midpoint => {
+				self.update_position(dt / 2.0);
+				self.update_velocity(dt);
+				self.update_position(dt / 2.0);
+			}
+			Integrator::RK4 => {
+				let k1 = (self.velocity, self.rot_velocity, self.acceleration, self.rot_accel);
+				let k2 = self.rk4_stage(dt / 2.0, k1, &mut accel);
+				let k3 = self.rk4_stage(dt / 2.0, k2, &mut accel);
+				let k4 = self.rk4_stage(dt, k3, &mut accel);
+				self.position += (dt / 6.0) * (k1.0 + 2.0 * k2.0 + 2.0 * k3.0 + k4.0);
+				self.rotation += (dt / 6.0) * (k1.1 + 2.0 * k2.1 + 2.0 * k3.1 + k4.1);
+				self.velocity += (dt / 6.0) * (k1.2 + 2.0 * k2.2 + 2.0 * k3.2 + k4.2);
+				self.rot_velocity += (dt / 6.0) * (k1.3 + 2.0 * k2.3 + 2.0 * k3.3 + k4.3);
+				self.wrap_rotation();
+			}
+		}
+	}
+
+	/// Derivatives at the state reached by moving along derivative `k` for `dt`.
+	fn rk4_stage(&self, dt: f32, k: Derivative, accel: &mut impl FnMut(&RigidBody) -> (vec2f, f32)) -> Derivative {
+		let state = Self {
+			position: self.position + dt * k.0,
+			rotation: self.rotation + dt * k.1,
+			velocity: self.velocity + dt * k.2,
+			rot_velocity: self.rot_velocity + dt * k.3,
+			..self.clone()
+		};
+		let (acceleration, rot_accel) = accel(&state);
+		(state.velocity, state.rot_velocity, acceleration, rot_accel)
+	}
+
 	pub fn tick_with_tilemap(&mut self, dt: f32, tilemap: &Tilemap, force: vec2f, torque: f32) {
 		// translation
 		self.acceleration = force / self.mass;
@@ -57,6 +140,8 @@ impl RigidBody {
 		//self.set_velocity((1.0 - dt * gs.linear_damping()) * self.velocity);
 		let delta_pos = dt * self.velocity;
 		let new_pos = self.position + delta_pos;
+		let x_offset = tilemap_x_offset(self.position);
+		let can_walk = |pos| tilemap.contact(pos, x_offset).is_none();
 		if can_walk(new_pos) { // 👈 hack for infite sized tile map
 			// no collision
 			self.position = new_pos;
@@ -99,7 +184,19 @@ impl RigidBody {
 	}
 
 	pub fn transform_rel_pos(&self, rel_pos: vec2f) -> vec2f {
-		(self.rotation_matrix() * rel_pos) + self.position
+		(self.rotation_matrix() * (rel_pos - self.center_of_mass)) + self.position
+	}
+
+	/// Move the center of mass (e.g. when burning fuel) without moving the body itself.
+	pub fn set_center_of_mass(&mut self, center_of_mass: vec2f) {
+		let delta = center_of_mass - self.center_of_mass;
+		self.position += self.rotation_matrix() * delta;
+		self.center_of_mass = center_of_mass;
+	}
+
+	/// Torque around the center of mass due to `force` applied at `rel_pos`.
+	pub fn torque(&self, rel_pos: vec2f, force: vec2f) -> f32 {
+		(self.rotation_matrix() * (rel_pos - self.center_of_mass)).cross(force)
 	}
 
 	pub fn transform_vector(&self, vector: vec2f) -> vec2f {
@@ -107,8 +204,9 @@ impl RigidBody {
 	}
 
 	pub fn velocity_of_rel_pos(&self, rel_pos: vec2f) -> vec2f {
-		let (x, y) = rel_pos.into();
-		let rot_vel = 2.0 * PI * self.rot_velocity * vec2(-y, x);
+		// rot_velocity is in rad/s, the lever arm is rotated along with the body
+		let (x, y) = (self.rotation_matrix() * (rel_pos - self.center_of_mass)).into();
+		let rot_vel = self.rot_velocity * vec2(-y, x);
 		rot_vel + self.velocity
 	}
 
@@ -120,3 +218,68 @@ impl RigidBody {
 		(self.transform_rel_pos(pos), self.transform_rotation(rot))
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Position error after one period of a unit harmonic oscillator.
+	fn oscillator_error(integrator: Integrator, dt: f32) -> f32 {
+		let mut body = RigidBody {
+			position: vec2(1.0, 0.0),
+			..default()
+		};
+		let steps = (2.0 * PI / dt).round() as usize;
+		for _ in 0..steps {
+			body.integrate(integrator, dt, |state| (-state.position, 0.0));
+		}
+		(body.position - vec2(1.0, 0.0)).len()
+	}
+
+	#[test]
+	fn torque_lever_arm_rotates_with_the_body() {
+		let mut body = RigidBody {
+			center_of_mass: vec2(1.0, 0.0),
+			..default()
+		};
+		// tail 2 m behind the CG, pushed down: nose up
+		let (tail, down) = (vec2(-1.0, 0.0), vec2(0.0, -1.0));
+		assert_eq!(body.torque(tail, down), 2.0);
+		// pitched 90° nose up, the tail is below the CG: pushing it down has no lever arm
+		body.rotation = PI / 2.0;
+		assert!(body.torque(tail, down).abs() < 1e-6);
+		assert!((body.torque(tail, vec2(1.0, 0.0)) - 2.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn velocity_of_rel_pos() {
+		let mut body = RigidBody {
+			velocity: vec2(10.0, 0.0),
+			rot_velocity: 0.5, // rad/s, nose up
+			center_of_mass: vec2(1.0, 0.0),
+			..default()
+		};
+		// 2 m ahead of the CG: moves up at 1 m/s
+		assert!((body.velocity_of_rel_pos(vec2(3.0, 0.0)) - vec2(10.0, 1.0)).len() < 1e-6);
+		// pitched 90° nose up, that point is above the CG: moves backwards
+		body.rotation = PI / 2.0;
+		assert!((body.velocity_of_rel_pos(vec2(3.0, 0.0)) - vec2(9.0, 0.0)).len() < 1e-6);
+		// matches the motion of the transformed point
+		let dt = 1e-3;
+		let before = body.transform_rel_pos(vec2(3.0, 0.0));
+		body.position += dt * body.velocity;
+		body.rotation += dt * body.rot_velocity;
+		let moved = (body.transform_rel_pos(vec2(3.0, 0.0)) - before) / dt;
+		assert!((moved - vec2(9.0, 0.0)).len() < 1e-2, "{moved:?}");
+	}
+
+	#[test]
+	fn higher_order_is_more_accurate() {
+		let dt = 0.1;
+		let euler = oscillator_error(Integrator::SymplecticEuler, dt);
+		let midpoint = oscillator_error(Integrator::Midpoint, dt);
+		let rk4 = oscillator_error(Integrator::RK4, dt);
+		assert!(rk4 < 1e-3, "{rk4}");
+		assert!(rk4 < midpoint && rk4 < euler, "{euler} {midpoint} {rk4}");
+	}
+}
@@ -12,66 +12,110 @@ pub struct Settings {
 }
 
 impl Settings {
-	pub fn todo_load_settings() -> Self {
+	/// Settings file, in the working directory.
+	pub const FILE: &'static str = "settings.toml";
+
+	/// Settings from `FILE`, with defaults for anything missing.
+	/// All defaults if the file is missing or invalid.
+	pub fn load() -> Self {
+		let file = Self::read().unwrap_or_else(|e| {
+			log::error!("{}: {e:#}", Self::FILE);
+			default()
+		});
 		Self {
-			graphics: default(),
-			controls: default(),
-			player: default(),
-			sound: default(),
-			network: default(),
-			debug: default(),
+			graphics: file.graphics,
+			controls: file.controls,
+			player: file.player,
+			sound: file.sound,
+			network: file.network,
+			debug: file.debug,
 		}
 	}
+
+	/// Store `controls` in `FILE`, keeping the other settings. Leaves an invalid file alone.
+	pub fn save_controls(controls: &Controls) -> Result<()> {
+		let mut file = Self::read()?;
+		if file.controls != *controls {
+			file.controls = controls.clone();
+			Self::write(&file)?;
+		}
+		Ok(())
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	fn read() -> Result<SettingsToml> {
+		match std::fs::read_to_string(Self::FILE) {
+			Ok(text) => Ok(toml::from_str(&text)?),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(default()),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	fn write(file: &SettingsToml) -> Result<()> {
+		Ok(std::fs::write(Self::FILE, toml::to_string(file)?)?)
+	}
+
+	// no settings file on the web
+	#[cfg(target_arch = "wasm32")]
+	fn read() -> Result<SettingsToml> {
+		Ok(default())
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	fn write(_: &SettingsToml) -> Result<()> {
+		Ok(())
+	}
 }
 
 /// All user-controlled settings, read from "settings.toml".
-#[derive(Deserialize, Default)]
-#[serde(deny_unknown_fields)]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
 struct SettingsToml {
 	pub graphics: GraphicsOpts,
 	pub controls: Controls,
 	pub player: PlayerOpts,
 	pub sound: SoundOpts,
 	pub network: NetworkOpts,
-	#[serde(default)]
 	pub debug: DebugOpts,
 }
 
-#[derive(Deserialize, Clone)]
-#[serde(deny_unknown_fields)]
+/// Key bindings and how fast keys move the controls.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect, PartialEq)]
+#[serde(default, deny_unknown_fields)]
 pub struct Controls {
-	pub forward: char,
-	pub left: char,
-	pub backward: char,
-	pub right: char,
-	pub crouch: char,
-	pub mouse_sensitivity: f32,
-	pub mouse_stutter_filter: u8,
-	pub mouse_smoothing: f32,
+	/// Keys for each action, by egui key name. E.g. `Pause = ["Space"]`.
+	#[inspect(hide)]
+	pub bindings: BTreeMap<Action, Vec<egui::Key>>,
+	/// Pitch input change per second while a pitch key is held (full travel: 2).
+	pub pitch_rate: f32,
+	/// Pitch input returns to center at this rate per second when no pitch key is held. 0: stays where it is.
+	pub pitch_centering: f32,
+	/// Throttle change per second (full travel: 1).
+	pub throttle_rate: f32,
+	/// Pitch trim change per second (full travel: 2).
+	pub trim_rate: f32,
 }
 
 impl Default for Controls {
 	fn default() -> Self {
 		Self {
-			forward: 'w',
-			left: 'a',
-			backward: 's',
-			right: 'd',
-			crouch: 'z',
-			mouse_sensitivity: 100.0,
-			mouse_stutter_filter: 1,
-			mouse_smoothing: 0.0,
+			bindings: Action::ALL.into_iter().map(|a| (a, a.default_keys())).collect(),
+			pitch_rate: 1.5,
+			pitch_centering: 0.0,
+			throttle_rate: 0.5,
+			trim_rate: 0.2,
 		}
 	}
 }
 
-#[derive(Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct PlayerOpts {
 	pub name: String,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct SoundOpts {
 	pub enabled: bool,
@@ -84,7 +128,7 @@ impl Default for SoundOpts {
 	}
 }
 
-#[derive(Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct NetworkOpts {
 	pub servers: Vec<String>,
@@ -98,7 +142,9 @@ pub struct DebugOpts {
 	pub draw_tilemap: bool,
 	pub draw_axes: bool,
 	pub force_record_plots: bool,
-	pub timepassage: u32,
+	/// Simulated seconds per wall-clock second. E.g. 0.25: slow motion.
+	pub timepassage: f32,
+	pub integrator: Integrator,
 }
 
 impl Default for DebugOpts {
@@ -109,13 +155,14 @@ impl Default for DebugOpts {
 			draw_tilemap: true,
 			draw_axes: true,
 			force_record_plots: false,
-			timepassage: 1,
+			timepassage: 1.0,
+			integrator: default(),
 		}
 	}
 }
 
 // User settings for graphics quality.
-#[derive(Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct GraphicsOpts {
 	pub width: u32,
@@ -181,3 +228,24 @@ impl Default for GraphicsOpts {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn controls_round_trip() {
+		let mut file = SettingsToml::default();
+		file.controls.bind(Action::Pause, egui::Key::P);
+		file.controls.pitch_centering = 0.5;
+		let text = toml::to_string(&file).unwrap();
+		let parsed: SettingsToml = toml::from_str(&text).unwrap();
+		assert_eq!(parsed.controls, file.controls);
+		assert!(parsed.controls.keys(Action::ParkingBrake).is_empty());
+
+		// anything missing is a default
+		let parsed: SettingsToml = toml::from_str("[controls]\npitch_rate = 2.0").unwrap();
+		assert_eq!(parsed.controls.pitch_rate, 2.0);
+		assert_eq!(parsed.controls.bindings, Controls::default().bindings);
+	}
+}
@@ -0,0 +1,184 @@
+use crate::prelude::*;
+
+/// Procedurally placed thermals: columns of rising air that form, peak and die out.
+///
+/// The world is cut into stretches of `spacing` meters, each holding at most one thermal,
+/// placed by hashing `seed` with the stretch index. So the endless world needs no list of thermals.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Thermals {
+	/// Peak climb rate in the core of the strongest thermals (m/s). 0: no thermals.
+	pub strength: f32,
+	/// Average distance between thermals (m).
+	pub spacing: f32,
+	/// Radius of the rising core (m), surrounded by a ring of weaker sink.
+	pub radius: f32,
+	/// Thermals are weak near the ground, full strength in between and die out at cloud base (altitude, m).
+	pub cloud_base: f32,
+	/// Each thermal builds up and decays over this period (s), then a new one forms.
+	pub lifetime: f32,
+	pub seed: u64,
+}
+
+/// Ridge lift: wind following the terrain, deflected up its windward slopes and down its lee slopes.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct RidgeLift {
+	/// Fraction of the wind that follows the terrain slope. 0: no ridge lift.
+	pub strength: f32,
+	/// Height above the terrain over which the deflection fades out (m).
+	/// The slope is averaged over the same distance, so single tile steps do not count as ridges.
+	pub depth: f32,
+}
+
+/// Total energy variometer, as used for soaring.
+/// Shows the climb rate of `altitude + airspeed²/2g`,
+/// so that trading speed for height (pulling up) does not show as lift.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Variometer {
+	/// Response time of the needle (s).
+	pub lag: f32,
+	/// Current reading (m/s).
+	pub reading: f32,
+}
+
+impl Thermals {
+	/// Vertical air velocity (m/s) at world position `pos`, time `t` (s), rising from `terrain`.
+	pub fn at(&self, pos: vec2f, t: f32, terrain: &Tilemap) -> f32 {
+		if self.strength == 0.0 || self.spacing <= 0.0 || self.radius <= 0.0 {
+			return 0.0;
+		}
+		// build up with height above the terrain, fade out towards cloud base
+		let height = (pos.y() - terrain.terrain_height(pos.x())) / self.cloud_base;
+		let below_base = 1.0 - pos.y() / self.cloud_base;
+		if height < 0.0 || below_base <= 0.0 {
+			return 0.0;
+		}
+		let profile = (5.0 * height).min(1.0) * (5.0 * below_base).min(1.0);
+
+		let cell = (pos.x() / self.spacing).floor() as i64;
+		let updraft: f32 = (cell - 1..=cell + 1)
+			.filter_map(|i| self.thermal(i))
+			.map(|(x, strength, phase)| {
+				let r2 = ((pos.x() - x) / self.radius).powi(2);
+				let life = 0.5 - 0.5 * f32::cos(2.0 * PI * (t / self.lifetime.max(1.0) + phase));
+				strength * life * (1.0 - r2) * f32::exp(-r2)
+			})
+			.sum();
+
+		updraft * profile
+	}
+
+	/// Position, peak strength and life cycle phase of the thermal in stretch `i`, if any.
+	fn thermal(&self, i: i64) -> Option<(f32, f32, f32)> {
+		let mut rng = Rng::new(self.seed ^ (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+		rng.next_u64(); // decorrelate neighbouring stretches
+		let present = rng.uniform() < 0.7;
+		let x = (i as f32 + rng.uniform()) * self.spacing;
+		let strength = self.strength * (0.4 + 0.6 * rng.uniform());
+		let phase = rng.uniform();
+		present.then_some((x, strength, phase))
+	}
+}
+
+impl RidgeLift {
+	/// Vertical air velocity (m/s) at `pos` for a horizontal `wind` (m/s, positive: towards +x) over `terrain`.
+	/// Positive where the terrain rises downwind, negative in the lee.
+	pub fn at(&self, pos: vec2f, wind: f32, terrain: &Tilemap) -> f32 {
+		if self.strength == 0.0 || self.depth <= 0.0 {
+			return 0.0;
+		}
+		let height = pos.y() - terrain.terrain_height(pos.x());
+		if height < 0.0 {
+			return 0.0;
+		}
+		let half = 0.5 * self.depth;
+		let slope = (terrain.terrain_height(pos.x() + half) - terrain.terrain_height(pos.x() - half)) / self.depth;
+		self.strength * wind * slope * f32::exp(-height / self.depth)
+	}
+}
+
+impl Variometer {
+	/// Follow the total energy climb rate of an aircraft moving at `air_velocity`
+	/// with vertical speed `climb` and `acceleration`.
+	pub fn tick(&mut self, dt: f32, climb: f32, air_velocity: vec2f, acceleration: vec2f, gravity: f32) {
+		let total_energy = climb + air_velocity.dot(acceleration) / gravity;
+		let a = (dt / self.lag.max(dt)).min(1.0);
+		self.reading += a * (total_energy - self.reading);
+	}
+}
+
+impl Default for Thermals {
+	fn default() -> Self {
+		Self {
+			strength: 0.0,
+			spacing: 1500.0,
+			radius: 150.0,
+			cloud_base: 1500.0,
+			lifetime: 900.0,
+			seed: 1,
+		}
+	}
+}
+
+impl Default for RidgeLift {
+	fn default() -> Self {
+		Self { strength: 1.0, depth: 100.0 }
+	}
+}
+
+impl Default for Variometer {
+	fn default() -> Self {
+		Self { lag: 1.0, reading: 0.0 }
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn thermals_rise_and_fade() {
+		let thermals = Thermals { strength: 3.0, ..default() };
+		let flat = Tilemap::new(vec2(1024, 1024), Tile::AIR);
+		let at = |x: f32, y: f32, t: f32| thermals.at(vec2(x, y), t, &flat);
+
+		// somewhere along the way, there is lift, and some sink
+		let samples = || (0..10000).map(|x| at(x as f32, 700.0, 0.0));
+		assert!(samples().any(|w| w > 1.0));
+		assert!(samples().any(|w| w < 0.0));
+		assert!(samples().all(|w| w <= thermals.strength));
+
+		// none above cloud base, weaker near the ground
+		assert!((0..10000).all(|x| at(x as f32, 1600.0, 0.0) == 0.0));
+		let peak = (0..10000).map(|x| x as f32).max_by(|&a, &b| at(a, 700.0, 0.0).total_cmp(&at(b, 700.0, 0.0))).unwrap();
+		assert!(at(peak, 50.0, 0.0) < at(peak, 700.0, 0.0));
+
+		// ... also over high ground
+		let mut hill = flat.clone();
+		hill.add_hill(peak, 200.0, 300.0);
+		assert!(thermals.at(vec2(peak, 350.0), 0.0, &hill) < 0.5 * at(peak, 350.0, 0.0));
+
+		// thermals come and go
+		assert!((0..10).any(|i| at(peak, 700.0, i as f32 * 100.0) < 0.5 * at(peak, 700.0, 0.0)));
+	}
+
+	#[test]
+	fn ridge_lift_on_the_windward_side() {
+		let mut terrain = Tilemap::new(vec2(1024, 1024), Tile::AIR);
+		terrain.add_hill(0.0, 100.0, 50.0);
+		let ridge = RidgeLift::default();
+		let windward = vec2(-50.0, terrain.terrain_height(-50.0) + 20.0);
+		let lee = vec2(50.0, terrain.terrain_height(50.0) + 20.0);
+
+		assert!(ridge.at(windward, 10.0, &terrain) > 2.0);
+		assert!(ridge.at(lee, 10.0, &terrain) < -2.0);
+		// the wind turned: lift on the other side
+		assert!(ridge.at(lee, -10.0, &terrain) > 2.0);
+		assert_eq!(ridge.at(windward, 0.0, &terrain), 0.0);
+		// fades with height, none over flat terrain
+		assert!(ridge.at(windward + vec2(0.0, 500.0), 10.0, &terrain).abs() < 0.1);
+		assert_eq!(ridge.at(vec2(-300.0, 30.0), 10.0, &terrain), 0.0);
+	}
+}
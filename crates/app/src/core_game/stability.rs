@@ -0,0 +1,237 @@
+use crate::prelude::*;
+use num_complex::Complex64;
+
+/// Linearized longitudinal dynamics around a trimmed state.
+///
+/// State: `[velocity x, velocity y, pitch, pitch rate]`, controls fixed at their trim positions.
+/// `matrix` is `A` in `dx/dt = A x`, its eigenvalues are the natural modes of motion.
+#[derive(Clone, Debug)]
+pub struct Stability {
+	pub trim: Trim,
+	pub matrix: [[f64; 4]; 4],
+	/// Short period first, then phugoid, then any aperiodic modes.
+	pub modes: Vec<Mode>,
+}
+
+/// A natural mode of motion: eigenvalue `λ` (only the one with positive imaginary part for an oscillation).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mode {
+	pub kind: ModeKind,
+	pub eigenvalue: Complex64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModeKind {
+	/// Fast, usually well damped pitch oscillation at nearly constant speed.
+	ShortPeriod,
+	/// Slow exchange of speed and height at nearly constant angle of attack.
+	Phugoid,
+	/// Non-oscillating convergence or divergence.
+	Aperiodic,
+}
+
+/// Pitch stability from the layout of the lifting surfaces and the CG, without flying.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StaticStability {
+	/// x of the neutral point, relative to the datum (m):
+	/// the lift-slope weighted average position of the surfaces, where a CG would give neutral pitch stability.
+	pub neutral_point: f32,
+	/// x of the center of gravity, relative to the datum (m).
+	pub cg: f32,
+	/// Distance of the CG ahead of the neutral point (m).
+	pub static_margin: f32,
+	/// Static margin as a fraction of the main wing chord (its drawn length).
+	pub static_margin_chord: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StabilityVerdict {
+	/// Returns to its angle of attack after a disturbance.
+	Stable,
+	/// Within `StaticStability::NEUTRAL_BAND`: hardly any tendency either way.
+	Neutral,
+	/// A disturbance in angle of attack grows: departs without constant pilot correction.
+	Unstable,
+}
+
+impl StaticStability {
+	/// Static margins within ± this fraction of the chord count as neutral.
+	pub const NEUTRAL_BAND: f32 = 0.02;
+
+	pub fn verdict(&self) -> StabilityVerdict {
+		match self.static_margin_chord {
+			m if m > Self::NEUTRAL_BAND => StabilityVerdict::Stable,
+			m if m < -Self::NEUTRAL_BAND => StabilityVerdict::Unstable,
+			_ => StabilityVerdict::Neutral,
+		}
+	}
+}
+
+impl Plane {
+	/// Neutral point and static margin for the current surfaces (incl. deflections) and loading.
+	/// Lift slopes are taken at each surface's incidence, for the fuselage aligned with the airflow.
+	/// None without lifting surfaces.
+	pub fn static_stability(&self) -> Option<StaticStability> {
+		const EPS: f32 = 1e-3;
+		let slopes = self.surfaces.iter().map(|s| {
+			let aoa = s.total_pitch();
+			(s.pos.x(), (s.curve.lift(aoa + EPS) - s.curve.lift(aoa - EPS)) / (2.0 * EPS))
+		});
+		let (moment, total): (f32, f32) = slopes.fold((0.0, 0.0), |(moment, total), (x, slope)| (moment + x * slope, total + slope));
+		if total <= 0.0 {
+			return None;
+		}
+
+		let neutral_point = moment / total;
+		let cg = self.mass_properties().cg.x();
+		let static_margin = cg - neutral_point;
+		let chord = self.main_wing().map_or(1.0, |w| w.size.x()).max(f32::EPSILON);
+		Some(StaticStability {
+			neutral_point,
+			cg,
+			static_margin,
+			static_margin_chord: static_margin / chord,
+		})
+	}
+
+	/// Trim at `airspeed` (m/s) and `climb_angle` (rad), then linearize `update_forces` around that state
+	/// and find the longitudinal modes.
+	pub fn stability(&self, airspeed: f32, climb_angle: f32) -> Result<Stability> {
+		let trim = self.trim(airspeed, climb_angle)?;
+		let mut trimmed = self.clone();
+		trimmed.set_trim(&trim);
+		let matrix = trimmed.state_matrix();
+
+		let mut oscillations = vec![];
+		let mut modes = vec![];
+		for eigenvalue in eigenvalues(matrix) {
+			if eigenvalue.im > 0.0 {
+				oscillations.push(eigenvalue);
+			} else if eigenvalue.im == 0.0 {
+				modes.push(Mode::new(ModeKind::Aperiodic, eigenvalue));
+			}
+		}
+		oscillations.sort_by(|a, b| b.norm().total_cmp(&a.norm()));
+		let kinds = match oscillations.len() {
+			2 => vec![ModeKind::ShortPeriod, ModeKind::Phugoid],
+			1 if oscillations[0].norm() > 1.0 => vec![ModeKind::ShortPeriod],
+			_ => vec![ModeKind::Phugoid],
+		};
+		let oscillating = iter::zip(kinds, oscillations).map(|(kind, eigenvalue)| Mode::new(kind, eigenvalue));
+		modes.splice(0..0, oscillating);
+
+		Ok(Stability { trim, matrix, modes })
+	}
+
+	/// `A` matrix by central differences of the accelerations from `update_forces`.
+	fn state_matrix(&self) -> [[f64; 4]; 4] {
+		const STEPS: [f32; 4] = [0.1, 0.1, 1e-3, 1e-2];
+		let derivative = |state: [f32; 4]| {
+			let mut plane = self.clone();
+			let body = &mut plane.body;
+			(body.velocity, body.rotation, body.rot_velocity) = (vec2(state[0], state[1]), state[2], state[3]);
+			plane.update_forces_airborne();
+			let body = &plane.body;
+			[body.acceleration.x(), body.acceleration.y(), body.rot_velocity, body.rot_accel]
+		};
+
+		let body = &self.body;
+		let state = [body.velocity.x(), body.velocity.y(), body.rotation, body.rot_velocity];
+		let mut matrix = [[0.0; 4]; 4];
+		for j in 0..4 {
+			let (mut plus, mut minus) = (state, state);
+			plus[j] += STEPS[j];
+			minus[j] -= STEPS[j];
+			let (plus, minus) = (derivative(plus), derivative(minus));
+			for i in 0..4 {
+				matrix[i][j] = (plus[i] - minus[i]) as f64 / (2.0 * STEPS[j] as f64);
+			}
+		}
+		matrix
+	}
+}
+
+impl Mode {
+	pub fn new(kind: ModeKind, eigenvalue: Complex64) -> Self {
+		Self { kind, eigenvalue }
+	}
+
+	/// Period of oscillation (s), None if aperiodic.
+	pub fn period(&self) -> Option<f64> {
+		(self.eigenvalue.im > 0.0).then(|| 2.0 * std::f64::consts::PI / self.eigenvalue.im)
+	}
+
+	/// 1: critically damped, 0: undamped, negative: growing.
+	pub fn damping_ratio(&self) -> f64 {
+		-self.eigenvalue.re / self.eigenvalue.norm().max(f64::MIN_POSITIVE)
+	}
+
+	pub fn is_stable(&self) -> bool {
+		self.eigenvalue.re < 0.0
+	}
+
+	/// Time for a disturbance to double in size (s), None if it does not grow.
+	pub fn time_to_double(&self) -> Option<f64> {
+		(self.eigenvalue.re > 0.0).then(|| std::f64::consts::LN_2 / self.eigenvalue.re)
+	}
+
+	/// Time for a disturbance to decay to half (s), None if it does not decay.
+	pub fn time_to_half(&self) -> Option<f64> {
+		(self.eigenvalue.re < 0.0).then(|| -std::f64::consts::LN_2 / self.eigenvalue.re)
+	}
+}
+
+impl fmt::Display for Mode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = match self.kind {
+			ModeKind::ShortPeriod => "short period",
+			ModeKind::Phugoid => "phugoid",
+			ModeKind::Aperiodic => "aperiodic",
+		};
+		write!(f, "{name}:")?;
+		if let Some(period) = self.period() {
+			write!(f, " period {period:.1} s, damping {:.2},", self.damping_ratio())?;
+		}
+		match (self.time_to_half(), self.time_to_double()) {
+			(Some(t), _) => write!(f, " halves in {t:.1} s"),
+			(_, Some(t)) => write!(f, " doubles in {t:.1} s"),
+			_ => write!(f, " neutral"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn trainer_modes() {
+		let mut plane = Plane::default();
+		plane.body.position = vec2(0.0, 500.0);
+		plane.air = Atmosphere::default().at(500.0);
+		let stability = plane.stability(65.0, 0.0).unwrap();
+		let find = |kind| stability.modes.iter().find(|m| m.kind == kind).expect("mode not found");
+		let (short, phugoid) = (find(ModeKind::ShortPeriod), find(ModeKind::Phugoid));
+		assert!(short.is_stable());
+		assert!(short.period().unwrap() < phugoid.period().unwrap());
+		assert!(short.damping_ratio() > phugoid.damping_ratio());
+		// Lanchester's approximation: T = π √2 V / g
+		let lanchester = PI as f64 * 2f64.sqrt() * 65.0 / 9.81;
+		assert!((phugoid.period().unwrap() / lanchester - 1.0).abs() < 0.2, "{phugoid}");
+	}
+
+	#[test]
+	fn neutral_point() {
+		let mut plane = Plane::default();
+		let stability = plane.static_stability().unwrap();
+		assert_eq!(stability.verdict(), StabilityVerdict::Stable);
+		assert!(stability.neutral_point < plane.main_wing().unwrap().pos.x());
+
+		// wing moved far forward: the CG is now behind the neutral point
+		plane.surfaces[0].pos[0] = 2.0;
+		assert_eq!(plane.static_stability().unwrap().verdict(), StabilityVerdict::Unstable);
+
+		plane.surfaces.clear();
+		assert_eq!(plane.static_stability(), None);
+	}
+}
@@ -9,13 +9,15 @@ impl Tile {
 	pub const CLOUD: Tile = Tile(1);
 	pub const TARMAC: Tile = Tile(2);
 	pub const LINE: Tile = Tile(3);
+	pub const GRASS: Tile = Tile(4);
+	pub const ICE: Tile = Tile(5);
 }
 
 impl Tile {
 	pub fn can_walk(self) -> bool {
 		match self {
 			Tile::AIR | Tile::CLOUD => true,
-			Tile::TARMAC | Tile::LINE => false,
+			Tile::TARMAC | Tile::LINE | Tile::GRASS | Tile::ICE => false,
 			_ => false,
 		}
 	}
@@ -26,6 +28,8 @@ impl Tile {
 			Self::CLOUD => (181, 194, 255),
 			Self::TARMAC => (50, 50, 50),
 			Self::LINE => (200, 200, 100),
+			Self::GRASS => (70, 120, 50),
+			Self::ICE => (210, 230, 250),
 			_ => (255, 0, 0),
 		})
 		.append(255)
@@ -33,9 +37,23 @@ impl Tile {
 
 	pub fn height(self) -> f32 {
 		match self {
-			Tile::TARMAC | Tile::LINE => 1.0,
+			Tile::TARMAC | Tile::LINE | Tile::GRASS | Tile::ICE => 1.0,
 			Tile::AIR | Tile::CLOUD => -0.5,
 			_ => 0.0,
 		}
 	}
+
+	/// Tyre friction on this surface.
+	pub fn friction(self) -> SurfaceFriction {
+		let (static_friction, sliding_friction, rolling_resistance) = match self {
+			Tile::GRASS => (0.5, 0.4, 0.08),
+			Tile::ICE => (0.1, 0.05, 0.01),
+			_ => (0.8, 0.6, 0.015),
+		};
+		SurfaceFriction {
+			static_friction,
+			sliding_friction,
+			rolling_resistance,
+		}
+	}
 }
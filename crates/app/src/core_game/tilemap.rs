@@ -32,6 +32,14 @@ impl Tilemap {
 			}
 		}
 
+		// grass and an icy patch at the end of each runway (the map repeats every TILEMAP_WRAP tiles, starting at column TILEMAP_WRAP/2)
+		for x in 700..740 {
+			map.try_set(vec2(x, h), Tile::GRASS);
+		}
+		for x in 740..768 {
+			map.try_set(vec2(x, h), Tile::ICE);
+		}
+
 		for y in 0..=h {
 			map.try_set(vec2(0, y), Tile::TARMAC);
 		}
@@ -71,11 +79,118 @@ impl Tilemap {
 		self.tiles.iter().map(|(pos, tile)| (pos, *tile))
 	}
 
-	pub fn _can_walk(&self, pos: vec2f) -> bool {
-		self.at_pos(pos.floor()).can_walk()
+	/// Penetration depth and surface normal if world position `pos` is inside solid terrain.
+	/// `x_offset` is the world x of tile column 0, see `tilemap_x_offset`.
+	/// The normal points towards the nearest tile face that borders air (up, left or right).
+	pub fn contact(&self, pos: vec2f, x_offset: f32) -> Option<Contact> {
+		let tile = Self::tile_index(pos, x_offset);
+		if !self.is_solid_tile(tile) {
+			return None;
+		}
+		let frac = pos - vec2(x_offset, 0.0) - tile.as_f32();
+		let faces = [(vec2(0, 1), 1.0 - frac.y()), (vec2(-1, 0), frac.x()), (vec2(1, 0), 1.0 - frac.x())];
+		let nearest = faces
+			.into_iter()
+			.filter_map(|(dir, to_edge)| {
+				self.solid_run(tile, dir).map(|n| Contact {
+					depth: to_edge + n as f32,
+					normal: dir.as_f32(),
+					surface: self.at_pos(tile + dir * n),
+				})
+			})
+			.min_by(|a, b| a.depth.total_cmp(&b.depth));
+
+		// deep inside terrain: push up
+		Some(nearest.unwrap_or(Contact {
+			depth: Self::MAX_CONTACT_DEPTH as f32,
+			normal: vec2::EY,
+			surface: Tile::TARMAC,
+		}))
+	}
+
+	/// World height of the terrain surface under `pos`:
+	/// top of the highest solid tile at or below `pos`.
+	pub fn surface_height(&self, pos: vec2f, x_offset: f32) -> f32 {
+		let mut tile = Self::tile_index(pos, x_offset);
+		tile[1] = tile.y().min(self.tiles.size.y() as i32);
+		while self.is_solid_tile(tile) && tile.y() < self.tiles.size.y() as i32 {
+			tile[1] += 1;
+		}
+		while !self.is_solid_tile(tile - vec2(0, 1)) {
+			tile[1] -= 1;
+		}
+		tile.y() as f32
+	}
+
+	/// Height of the terrain surface at world `x` (m).
+	pub fn terrain_height(&self, x: f32) -> f32 {
+		let pos = vec2(x, 0.0);
+		self.surface_height(pos, tilemap_x_offset(pos))
+	}
+
+	/// Raise a grass hill `height` (m) high on the terrain, with its top at world `x` and slopes `half_width` (m) long.
+	/// Like all terrain, it repeats every `TILEMAP_WRAP` m.
+	pub fn add_hill(&mut self, x: f32, half_width: f32, height: f32) {
+		for dx in (-half_width as i32)..=(half_width as i32) {
+			let pos = vec2(x + dx as f32, 0.0);
+			let column = Self::tile_index(pos, tilemap_x_offset(pos)).x();
+			let ground = self.terrain_height(pos.x()) as i32;
+			let bump = (height * (1.0 - dx.abs() as f32 / half_width.max(1.0))).round() as i32;
+			for y in ground..ground + bump {
+				self.try_set(vec2(column, y), Tile::GRASS);
+			}
+		}
+	}
+
+	const MAX_CONTACT_DEPTH: i32 = 2;
+
+	fn tile_index(pos: vec2f, x_offset: f32) -> Pos {
+		(pos - vec2(x_offset, 0.0)).floor()
 	}
+
+	/// Everything below the map counts as solid ground.
+	fn is_solid_tile(&self, tile: Pos) -> bool {
+		tile.y() < 0 || !self.at_pos(tile).can_walk()
+	}
+
+	/// Number of solid tiles after `tile` in direction `dir` before reaching air (if within `MAX_CONTACT_DEPTH`).
+	fn solid_run(&self, tile: Pos, dir: vec2i) -> Option<i32> {
+		(0..Self::MAX_CONTACT_DEPTH).find(|&n| !self.is_solid_tile(tile + dir * (n + 1)))
+	}
+}
+
+/// A point penetrating solid terrain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+	/// Penetration depth (m).
+	pub depth: f32,
+	/// Unit surface normal, pointing out of the terrain.
+	pub normal: vec2f,
+	/// Tile at the surface, determines the tyre friction.
+	pub surface: Tile,
 }
 
-pub fn can_walk(pos: vec2f) -> bool {
-	pos.y() > 5.0
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn contact_with_ground_and_walls() {
+		let mut map = Tilemap::new(vec2(16, 16), Tile::AIR);
+		for x in 0..16 {
+			map.try_set(vec2(x, 0), Tile::TARMAC);
+		}
+		map.try_set(vec2(8, 1), Tile::TARMAC); // a 1x1 building
+
+		assert_eq!(map.contact(vec2(3.5, 1.5), 0.0), None);
+		assert_eq!(map.contact(vec2(3.5, 0.75), 0.0).map(|c| c.normal), Some(vec2::EY));
+		assert!((map.contact(vec2(3.5, 0.75), 0.0).unwrap().depth - 0.25).abs() < 1e-6);
+		assert_eq!(map.contact(vec2(8.1, 1.5), 0.0).map(|c| c.normal), Some(-vec2::EX));
+		assert_eq!(map.contact(vec2(-3.5, 0.75), -10.0).map(|c| c.normal), Some(vec2::EY));
+		assert_eq!(map.contact(vec2(3.5, -5.0), 0.0).map(|c| c.normal), Some(vec2::EY)); // below the map
+
+		assert_eq!(map.surface_height(vec2(3.5, 10.0), 0.0), 1.0);
+		assert_eq!(map.surface_height(vec2(8.5, 10.0), 0.0), 2.0);
+		assert_eq!(map.surface_height(vec2(8.5, 1.5), 0.0), 2.0);
+	}
 }
@@ -0,0 +1,226 @@
+use crate::prelude::*;
+
+/// Steady, unaccelerated flight found by `Plane::trim`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trim {
+	/// True airspeed (m/s).
+	pub airspeed: f32,
+	/// Flight path angle relative to the air (rad, positive: climbing).
+	pub climb_angle: f32,
+	/// Pitch input (`ControlInputs::pitch` + `pitch_trim`) holding the nose.
+	pub pitch_input: f32,
+	pub throttle: f32,
+	/// Propeller thrust (N).
+	pub thrust: f32,
+	/// Body pitch (rad).
+	pub pitch: f32,
+}
+
+impl Plane {
+	/// Find the pitch input, throttle and body pitch for steady flight at `airspeed` (m/s, true)
+	/// and `climb_angle` (rad), with the current flaps, gear and loading, where net force and torque vanish.
+	/// Ground contact is ignored.
+	///
+	/// Fails if no such state exists: not enough elevator authority or engine power, or the wing stalls.
+	pub fn trim(&self, airspeed: f32, climb_angle: f32) -> Result<Trim> {
+		let mut trim = self.trim_with_thrust(airspeed, climb_angle)?;
+		trim.throttle = self.throttle_for_thrust(trim.thrust, airspeed)?;
+		Ok(trim)
+	}
+
+	/// Like `trim`, but with whatever thrust it takes, regardless of the engine (`throttle` is left at 0).
+	pub fn trim_with_thrust(&self, airspeed: f32, climb_angle: f32) -> Result<Trim> {
+		if airspeed <= 1.0 {
+			bail!("airspeed too low");
+		}
+		if !self.surfaces.iter().any(|s| s.control == ControlAxis::Pitch && s.max_deflection != 0.0) {
+			bail!("no pitch control");
+		}
+
+		// Unknowns: pitch input, thrust / weight and body pitch. Thrust is solved for directly
+		// (rather than throttle) because it does not saturate, throttle is found afterwards.
+		let mut x = [self.controls.pitch + self.controls.pitch_trim, 0.1, climb_angle + 3.0 * DEG];
+		const STEP: f32 = 1e-3;
+		let mut converged = false;
+		for _ in 0..50 {
+			let r = self.trim_residual(airspeed, climb_angle, x);
+			if r.iter().all(|v| v.abs() < 1e-3) {
+				converged = true;
+				break;
+			}
+			let mut jacobian = [[0.0; 3]; 3];
+			for j in 0..3 {
+				let mut x2 = x;
+				x2[j] += STEP;
+				let r2 = self.trim_residual(airspeed, climb_angle, x2);
+				for i in 0..3 {
+					jacobian[i][j] = (r2[i] - r[i]) / STEP;
+				}
+			}
+			let Some(dx) = solve_linear(jacobian, r) else { bail!("no trim: singular") };
+			for j in 0..3 {
+				x[j] -= dx[j].clamp(-0.5, 0.5); // don't jump to another branch (e.g. inverted flight)
+			}
+		}
+		let [pitch_input, thrust_to_weight, pitch] = x;
+
+		if !converged {
+			bail!("no trim: solver did not converge");
+		}
+		if pitch_input.abs() > 1.0 {
+			bail!("no trim: needs {:.0}% pitch input", 100.0 * pitch_input);
+		}
+		let indicated_airspeed = self.air.indicated_airspeed(airspeed);
+		for surface in self.surfaces.iter().filter(|s| s.control == ControlAxis::Pitch) {
+			let deflection = pitch_input * surface.max_deflection;
+			if surface.actuator.reachable(deflection, indicated_airspeed) != deflection {
+				bail!("no trim: '{}' can't reach {:.1}° deflection", surface.name, deflection / DEG);
+			}
+		}
+		let mut trimmed = self.clone();
+		trimmed.set_trim_state(airspeed, climb_angle, pitch_input, pitch);
+		if trimmed.main_wing().is_some_and(|w| trimmed.winglet_is_stalled(w)) {
+			bail!("no trim: wing stalled");
+		}
+
+		Ok(Trim {
+			airspeed,
+			climb_angle,
+			pitch_input,
+			throttle: 0.0,
+			thrust: thrust_to_weight * self.body.mass * self.gravity,
+			pitch,
+		})
+	}
+
+	/// Steepest steady descent at `airspeed` (m/s, true) with the throttle at idle (rad, positive: descending).
+	/// Any steeper and the aircraft keeps accelerating.
+	pub fn steepest_descent(&self, airspeed: f32) -> Result<f32> {
+		let mut engine = self.engine.clone();
+		engine.throttle = 0.0;
+		engine.rpm = engine.target_rpm();
+		let idle = engine.thrust(airspeed, &self.air);
+		let too_steep = |angle: f32| self.trim_with_thrust(airspeed, -angle).map(|trim| trim.thrust < idle);
+		if too_steep(0.0)? {
+			bail!("no trim: still accelerating at idle in level flight");
+		}
+		// no trim at all (e.g. out of elevator) counts as too steep
+		let (mut lo, mut hi) = (0.0, 30.0 * DEG);
+		for _ in 0..20 {
+			let mid = 0.5 * (lo + hi);
+			if too_steep(mid).unwrap_or(true) {
+				hi = mid;
+			} else {
+				lo = mid;
+			}
+		}
+		Ok(lo)
+	}
+
+	/// Put the aircraft in a trimmed state (keeping its position), hands off: the pitch input is all trim.
+	pub fn set_trim(&mut self, trim: &Trim) {
+		self.set_trim_state(trim.airspeed, trim.climb_angle, trim.pitch_input, trim.pitch);
+		self.controls.pitch = 0.0;
+		self.controls.pitch_trim = trim.pitch_input;
+		self.engine.throttle = trim.throttle;
+		self.engine.rpm = self.engine.target_rpm();
+	}
+
+	/// Pose, velocity and surface deflections. Pitch input is not clamped, so the solver sees a smooth function.
+	fn set_trim_state(&mut self, airspeed: f32, climb_angle: f32, pitch_input: f32, pitch: f32) {
+		self.body.rotation = pitch;
+		self.body.rot_velocity = 0.0;
+		self.body.velocity = self.wind + airspeed * vec2(climb_angle.cos(), climb_angle.sin());
+		let indicated_airspeed = self.air.indicated_airspeed(airspeed);
+		for surface in &mut self.surfaces {
+			surface.apply_controls(&self.controls, indicated_airspeed);
+			if surface.control == ControlAxis::Pitch {
+				surface.deflection = pitch_input * surface.max_deflection;
+			}
+		}
+	}
+
+	/// Linear and angular acceleration for trim unknowns `[pitch input, thrust / weight, body pitch]`.
+	fn trim_residual(&self, airspeed: f32, climb_angle: f32, [pitch_input, thrust_to_weight, pitch]: [f32; 3]) -> [f32; 3] {
+		let mut plane = self.clone();
+		plane.set_trim_state(airspeed, climb_angle, pitch_input, pitch);
+		plane.engine.rpm = 0.0; // thrust added below
+		plane.update_forces_airborne();
+
+		let thrust = thrust_to_weight * plane.body.mass * plane.gravity * (plane.body.rotation_matrix() * vec2::EX);
+		let body = &plane.body;
+		let acceleration = body.acceleration + thrust / body.mass;
+		let rot_accel = body.rot_accel + body.torque(plane.propeller_pos, thrust) / body.rot_inertia;
+		[acceleration.x(), acceleration.y(), rot_accel]
+	}
+
+	/// Throttle setting giving `thrust` (N) at `airspeed`, once the engine has spooled up.
+	fn throttle_for_thrust(&self, thrust: f32, airspeed: f32) -> Result<f32> {
+		let mut engine = self.engine.clone();
+		let mut thrust_at = |throttle: f32| {
+			engine.throttle = throttle;
+			engine.rpm = engine.target_rpm();
+			engine.thrust(airspeed, &self.air)
+		};
+
+		let (idle, full) = (thrust_at(0.0), thrust_at(1.0));
+		if thrust < idle {
+			bail!("no trim: too steep, still accelerating at idle");
+		}
+		if thrust > full {
+			bail!("no trim: needs {:.0}% of the available thrust", 100.0 * thrust / full.max(1.0));
+		}
+		// thrust increases with throttle: bisect
+		let (mut lo, mut hi) = (0.0, 1.0);
+		for _ in 0..30 {
+			let mid = 0.5 * (lo + hi);
+			if thrust_at(mid) < thrust {
+				lo = mid;
+			} else {
+				hi = mid;
+			}
+		}
+		Ok(0.5 * (lo + hi))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn trim_level_flight_and_climb() {
+		let mut plane = Plane::default();
+		plane.body.position = vec2(0.0, 500.0);
+		plane.air = Atmosphere::default().at(500.0);
+
+		let level = plane.trim(65.0, 0.0).unwrap();
+		assert!((0.0..1.0).contains(&level.throttle), "{level:?}");
+		let climb = plane.trim(65.0, 1.5 * DEG).unwrap();
+		assert!(climb.throttle > level.throttle, "{climb:?}");
+
+		// stays put when flown
+		plane.set_trim(&level);
+		let sky = Tilemap::new(vec2(1, 1), Tile::AIR);
+		for _ in 0..1000 {
+			plane.tick(0.001, &sky, Integrator::RK4);
+		}
+		assert!((plane.true_airspeed() - 65.0).abs() < 0.1, "{}", plane.true_airspeed());
+		assert!(plane.body.velocity.y().abs() < 0.1, "{}", plane.body.velocity.y());
+
+		assert!(plane.trim(100.0, 0.0).is_err(), "too fast");
+		assert!(plane.trim(40.0, 0.0).is_err(), "too slow");
+	}
+
+	#[test]
+	fn steepest_descent_at_idle() {
+		let mut plane = Plane::default();
+		plane.body.position = vec2(0.0, 500.0);
+		plane.air = Atmosphere::default().at(500.0);
+
+		let angle = plane.steepest_descent(65.0).unwrap();
+		assert!((2.0 * DEG..10.0 * DEG).contains(&angle), "{}°", angle / DEG);
+		assert!(plane.trim(65.0, -0.99 * angle).unwrap().throttle < 0.05);
+		assert!(plane.trim(65.0, -1.01 * angle).is_err());
+	}
+}
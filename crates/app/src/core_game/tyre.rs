@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+/// Tyre friction of a runway surface.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, EguiInspect)]
+pub struct SurfaceFriction {
+	/// Friction coefficient of a rolling (braked but not locked) tyre.
+	pub static_friction: f32,
+	/// Friction coefficient of a locked, skidding tyre.
+	pub sliding_friction: f32,
+	/// Rolling resistance coefficient.
+	pub rolling_resistance: f32,
+}
+
+/// Tyres and wheel brakes.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Tyre {
+	/// Brake force per braked wheel at full brake input (N).
+	/// Limited by the surface friction, beyond which the wheel locks and skids.
+	pub max_brake_force: f32,
+	/// Which of `Plane::wheels` have brakes.
+	pub braked: [bool; 2],
+}
+
+/// Force along the surface exerted by a tyre.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TyreForce {
+	/// Along the surface tangent (N).
+	pub force: f32,
+	/// Wheel locked by the brakes, sliding over the surface.
+	pub skidding: bool,
+}
+
+impl Tyre {
+	/// Below this speed (m/s) the wheel counts as standing still: it holds against the applied force.
+	const SLIP_SPEED: f32 = 0.1;
+
+	/// Rolling resistance plus brake force (N), up to what the surface can take without skidding.
+	/// `brake`: 0..1.
+	pub fn holding_force(&self, surface: &SurfaceFriction, normal_force: f32, brake: f32, braked: bool) -> f32 {
+		let rolling = surface.rolling_resistance * normal_force;
+		let brake = if braked { brake.clamp(0.0, 1.0) * self.max_brake_force } else { 0.0 };
+		(rolling + brake).min(surface.static_friction * normal_force)
+	}
+
+	/// Rolling resistance plus brake force, for a wheel pressed on the surface with `normal_force`
+	/// and moving at `v_tangent` along the surface. `brake`: 0..1.
+	/// `applied`: this wheel's share of the other forces along the surface (N), which a wheel at rest holds against.
+	pub fn force(&self, surface: &SurfaceFriction, normal_force: f32, v_tangent: f32, brake: f32, braked: bool, applied: f32) -> TyreForce {
+		let rolling = surface.rolling_resistance * normal_force;
+		let demand = rolling + if braked { brake.clamp(0.0, 1.0) * self.max_brake_force } else { 0.0 };
+		let holding = self.holding_force(surface, normal_force, brake, braked);
+
+		let skidding = demand > surface.static_friction * normal_force && v_tangent.abs() > Self::SLIP_SPEED;
+		let force = if skidding {
+			-surface.sliding_friction * normal_force * v_tangent.signum()
+		} else if v_tangent.abs() <= Self::SLIP_SPEED {
+			// static friction: cancel the applied force and damp out any creep
+			(-applied - holding * v_tangent / Self::SLIP_SPEED).clamp(-holding, holding)
+		} else {
+			-holding * v_tangent.signum()
+		};
+		TyreForce { force, skidding }
+	}
+}
+
+impl Default for Tyre {
+	fn default() -> Self {
+		Self {
+			max_brake_force: 4000.0,
+			braked: [false, true],
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn brakes_lock_on_ice() {
+		let tyre = Tyre::default();
+		let (n, v) = (8000.0, 20.0);
+
+		let rolling = tyre.force(&Tile::TARMAC.friction(), n, v, 0.0, true, 0.0);
+		assert!(rolling.force < 0.0 && !rolling.skidding);
+
+		let tarmac = tyre.force(&Tile::TARMAC.friction(), n, v, 1.0, true, 0.0);
+		assert!(!tarmac.skidding);
+		assert!(tarmac.force < -tyre.max_brake_force);
+
+		let ice = tyre.force(&Tile::ICE.friction(), n, v, 1.0, true, 0.0);
+		assert!(ice.skidding);
+		assert!(ice.force > tarmac.force / 4.0);
+
+		let unbraked = tyre.force(&Tile::ICE.friction(), n, v, 1.0, false, 0.0);
+		assert!(!unbraked.skidding);
+	}
+
+	#[test]
+	fn parked_with_idle_thrust_stays_put() {
+		let mut gs = GameState::default();
+		gs.plane.controls.parking_brake = true;
+		gs.plane.engine.throttle = 0.0;
+		for _ in 0..2000 {
+			gs.inner_tick();
+		}
+		assert!(gs.plane.weight_on_wheels() && gs.plane.propeller_thrust() > 0.0);
+		let parked = gs.plane.position();
+		for _ in 0..10_000 {
+			gs.inner_tick();
+		}
+		// only the suspension settling a little, no creeping (the brakes used to give way at rest)
+		let moved = gs.plane.position().x() - parked.x();
+		assert!(moved.abs() < 2e-3 && gs.plane.body.velocity.x().abs() < 1e-4, "crept {moved} m");
+	}
+}
@@ -0,0 +1,253 @@
+use crate::prelude::*;
+
+/// Moving air: steady wind by altitude, discrete gusts, microbursts, random turbulence,
+/// thermals and ridge lift.
+///
+/// The aircraft experiences `WindField::at` as the velocity of the air around it,
+/// which is subtracted from its ground velocity to obtain airspeed and angle of attack.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct WindField {
+	/// Steady horizontal wind, linearly interpolated between layers (sorted by altitude).
+	/// Different speeds at different altitudes cause wind shear.
+	pub layers: Vec<WindLayer>,
+	pub gusts: Vec<Gust>,
+	pub microbursts: Vec<Microburst>,
+	pub turbulence: Turbulence,
+	pub thermals: Thermals,
+	pub ridge_lift: RidgeLift,
+}
+
+/// Steady horizontal wind at some altitude.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect, PartialEq)]
+pub struct WindLayer {
+	/// m
+	pub altitude: f32,
+	/// m/s, positive: blowing towards +x (tailwind when flying to the right).
+	pub speed: f32,
+}
+
+/// Discrete "1 - cos" gust.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect)]
+pub struct Gust {
+	/// s
+	pub start_time: f32,
+	/// s
+	pub duration: f32,
+	/// Peak wind velocity (m/s).
+	pub amplitude: vec2f,
+}
+
+/// Column of sinking air that spreads out horizontally near the ground.
+/// Flying through it gives a headwind (more lift), then a downdraft, then a tailwind (less lift).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, EguiInspect)]
+pub struct Microburst {
+	/// Center of the downdraft (m).
+	pub x: f32,
+	/// m
+	pub radius: f32,
+	/// Peak sink speed in the core (m/s).
+	pub downdraft: f32,
+	/// Height of the horizontal outflow layer (m).
+	pub outflow_height: f32,
+}
+
+/// Dryden-style turbulence: horizontal and vertical components are
+/// first-order filtered white noise with correlation length `length_scale`.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect)]
+#[serde(default)]
+pub struct Turbulence {
+	/// RMS turbulence velocity (m/s). 0: calm, ~1.5: light, ~3: moderate, ~6: severe.
+	pub intensity: f32,
+	/// m
+	pub length_scale: f32,
+	pub seed: u64,
+
+	/// Current turbulent velocity.
+	#[inspect(hide)]
+	pub velocity: vec2f,
+	#[inspect(hide)]
+	rng: Rng,
+}
+
+impl WindField {
+	/// Wind velocity (m/s) at world position `pos`, time `t` (s), over `terrain`.
+	pub fn at(&self, pos: vec2f, t: f32, terrain: &Tilemap) -> vec2f {
+		let altitude = pos.y();
+		let steady_wind = self.steady_wind(altitude);
+		let mut wind = vec2(steady_wind, 0.0);
+		for gust in &self.gusts {
+			wind += gust.at(t);
+		}
+		for microburst in &self.microbursts {
+			wind += microburst.at(pos);
+		}
+		let updraft = self.thermals.at(pos, t, terrain) + self.ridge_lift.at(pos, steady_wind, terrain);
+		wind + vec2(0.0, updraft) + self.turbulence.velocity
+	}
+
+	/// Steady horizontal wind speed at `altitude`, interpolated between layers.
+	pub fn steady_wind(&self, altitude: f32) -> f32 {
+		match self.layers.as_slice() {
+			[] => 0.0,
+			[first, ..] if altitude <= first.altitude => first.speed,
+			[.., last] if altitude >= last.altitude => last.speed,
+			layers => {
+				let i = layers.iter().position(|l| l.altitude > altitude).unwrap_or(layers.len() - 1).max(1);
+				let (a, b) = (layers[i - 1], layers[i]);
+				let frac = (altitude - a.altitude) / (b.altitude - a.altitude).max(f32::EPSILON);
+				a.speed + frac * (b.speed - a.speed)
+			}
+		}
+	}
+
+	/// Advance the turbulence by `dt` for an aircraft flying at `airspeed`.
+	pub fn tick(&mut self, dt: f32, airspeed: f32, t: f32) {
+		self.turbulence.tick(dt, airspeed);
+		self.gusts.retain(|g| t < g.start_time + g.duration);
+	}
+
+	/// Steady wind that increases logarithmically from `surface_wind` at 10 m.
+	pub fn with_surface_wind(surface_wind: f32) -> Self {
+		Self {
+			layers: [10.0, 100.0, 500.0, 2000.0]
+				.into_iter()
+				.map(|altitude| WindLayer {
+					altitude,
+					speed: surface_wind * (1.0 + 0.15 * (altitude / 10.0).ln()),
+				})
+				.collect(),
+			..default()
+		}
+	}
+}
+
+impl Gust {
+	pub fn at(&self, t: f32) -> vec2f {
+		let phase = (t - self.start_time) / self.duration;
+		if (0.0..1.0).contains(&phase) {
+			self.amplitude * (0.5 * (1.0 - f32::cos(2.0 * PI * phase)))
+		} else {
+			vec::ZERO
+		}
+	}
+}
+
+impl Microburst {
+	pub fn at(&self, pos: vec2f) -> vec2f {
+		let dx = (pos.x() - self.x) / self.radius;
+		let h = (pos.y() / self.outflow_height).max(0.0);
+		let core = f32::exp(-dx * dx);
+
+		// downdraft weakens close to the ground, where the air turns outward
+		let sink = -self.downdraft * core * h.min(1.0);
+		// outflow is strongest at the ground and vanishes above the outflow layer
+		let outflow = 2.0 * self.downdraft * dx * core * (1.0 - h).max(0.0);
+
+		vec2(outflow, sink)
+	}
+}
+
+impl Turbulence {
+	pub fn tick(&mut self, dt: f32, airspeed: f32) {
+		if self.intensity <= 0.0 {
+			self.velocity = vec::ZERO;
+			return;
+		}
+		// first-order Gauss-Markov process with the Dryden spatial correlation,
+		// converted to time correlation by flying through it at `airspeed`.
+		let a = f32::exp(-airspeed.max(1.0) * dt / self.length_scale.max(1.0));
+		let b = self.intensity * (1.0 - a * a).sqrt();
+		let noise = vec2(self.rng.normal(), self.rng.normal());
+		self.velocity = a * self.velocity + b * noise;
+	}
+
+	/// Restart the random sequence from `seed`.
+	pub fn reseed(&mut self) {
+		self.rng = Rng::new(self.seed);
+		self.velocity = vec::ZERO;
+	}
+}
+
+impl Default for WindField {
+	fn default() -> Self {
+		Self {
+			layers: vec![],
+			gusts: vec![],
+			microbursts: vec![],
+			turbulence: default(),
+			thermals: default(),
+			ridge_lift: default(),
+		}
+	}
+}
+
+impl Default for Turbulence {
+	fn default() -> Self {
+		Self {
+			intensity: 0.0,
+			length_scale: 500.0,
+			seed: 1,
+			velocity: vec::ZERO,
+			rng: Rng::new(1),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn steady_wind_layers() {
+		let mut wind = WindField::default();
+		assert_eq!(wind.steady_wind(100.0), 0.0);
+		wind.layers = vec![WindLayer { altitude: 100.0, speed: 5.0 }, WindLayer { altitude: 300.0, speed: -5.0 }];
+		assert_eq!(wind.steady_wind(0.0), 5.0);
+		assert_eq!(wind.steady_wind(200.0), 0.0);
+		assert_eq!(wind.steady_wind(250.0), -2.5);
+		assert_eq!(wind.steady_wind(1000.0), -5.0);
+
+		// stronger higher up
+		let wind = WindField::with_surface_wind(5.0);
+		assert!(wind.steady_wind(10.0) == 5.0 && wind.steady_wind(500.0) > 5.0);
+	}
+
+	#[test]
+	fn microburst() {
+		let burst = Microburst {
+			x: 1000.0,
+			radius: 500.0,
+			downdraft: 10.0,
+			outflow_height: 300.0,
+		};
+		// sinks in the core, above the outflow layer
+		assert_eq!(burst.at(vec2(1000.0, 500.0)), vec2(0.0, -10.0));
+		// spreads out near the ground: headwind before the core, tailwind after
+		let (before, after) = (burst.at(vec2(600.0, 0.0)), burst.at(vec2(1400.0, 0.0)));
+		assert!(before.x() < 0.0 && after.x() > 0.0 && before.y() == 0.0, "{before:?} {after:?}");
+		assert!((before.x() + after.x()).abs() < 1e-4);
+		// far away: calm
+		assert!(burst.at(vec2(5000.0, 0.0)).len() < 1e-3);
+	}
+
+	#[test]
+	fn gust_timing() {
+		let gust = Gust {
+			start_time: 10.0,
+			duration: 2.0,
+			amplitude: vec2(0.0, 4.0),
+		};
+		assert_eq!(gust.at(9.9), vec::ZERO);
+		assert!(gust.at(10.0).len() < 1e-6);
+		assert!((gust.at(11.0).y() - 4.0).abs() < 1e-5);
+		assert_eq!(gust.at(12.0), vec::ZERO);
+
+		let mut wind = WindField { gusts: vec![gust], ..default() };
+		assert!((wind.at(vec2(0.0, 100.0), 11.0, &Tilemap::new(vec2(1, 1), Tile::AIR)).y() - 4.0).abs() < 1e-5);
+		wind.tick(0.001, 50.0, 11.0);
+		assert_eq!(wind.gusts.len(), 1);
+		wind.tick(0.001, 50.0, 12.0);
+		assert!(wind.gusts.is_empty());
+	}
+}
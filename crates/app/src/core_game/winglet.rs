@@ -1,9 +1,237 @@
 use crate::prelude::*;
 
-#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect, Setters, Copy)]
+/// A lifting surface: wing, flap, slat, canard, horizontal tail, spoiler...
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect, Setters)]
+#[serde(default)]
 pub struct Winglet {
+	pub name: String,
+	pub kind: SurfaceKind,
 	pub pos: vec2f,
+	/// Incidence relative to the fuselage (rad).
 	pub pitch: f32,
-	pub drag_factor: f32,
-	pub lift_to_drag: f32,
+	pub curve: LiftCurve,
+	/// Zero-lift pitching moment per airspeed² (N m / (m/s)², positive: nose up).
+	/// Cambered wings pitch the nose down (negative).
+	pub pitching_moment: f32,
+
+	/// Pilot input that moves this surface.
+	pub control: ControlAxis,
+	/// Deflection (rad) at full control input. Negative reverses the control (e.g. canard vs. tail).
+	pub max_deflection: f32,
+	/// Moves the surface to the commanded deflection.
+	pub actuator: Actuator,
+	/// Current deflection (rad), added to `pitch`.
+	#[inspect(hide)]
+	#[serde(skip)]
+	pub deflection: f32,
+	/// Terrain height under the surface, relative to `Plane::ground_level` (m). Updated every tick, for ground effect.
+	#[inspect(hide)]
+	#[serde(skip)]
+	pub ground_offset: f32,
+
+	/// Span of the wing this surface belongs to (m), for ground effect. 0: no ground effect.
+	pub span: f32,
+	/// Drawn size (m).
+	pub size: vec2f,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurfaceKind {
+	MainWing,
+	Flap,
+	Slat,
+	Canard,
+	HorizontalTail,
+	Spoiler,
+}
+
+/// Pilot inputs that can drive a surface's deflection.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlAxis {
+	Fixed,
+	/// -1..1
+	Pitch,
+	/// 0..1
+	Flaps,
+	/// 0..1
+	Spoilers,
+}
+
+/// Current pilot control positions.
+#[derive(Serialize, Deserialize, Clone, Debug, EguiInspect, Default)]
+#[serde(default)]
+pub struct ControlInputs {
+	/// -1..1, positive: nose down (stick forward).
+	pub pitch: f32,
+	/// -1..1, added to `pitch`: moves the neutral stick position.
+	pub pitch_trim: f32,
+	/// 0..1
+	pub flaps: f32,
+	/// 0..1
+	pub spoilers: f32,
+	/// Wheel brakes 0..1.
+	pub brakes: f32,
+	pub parking_brake: bool,
+}
+
+impl Winglet {
+	pub fn new(name: &str, kind: SurfaceKind, pos: vec2f, pitch: f32, curve: LiftCurve) -> Self {
+		Self {
+			name: name.into(),
+			kind,
+			pos,
+			pitch,
+			curve,
+			..default()
+		}
+	}
+
+	/// Incidence including control deflection (rad).
+	pub fn total_pitch(&self) -> f32 {
+		self.pitch + self.deflection
+	}
+
+	pub fn with_control(self, control: ControlAxis, max_deflection: f32) -> Self {
+		Self { control, max_deflection, ..self }
+	}
+
+	pub fn with_size(self, size: vec2f) -> Self {
+		Self { size, ..self }
+	}
+
+	pub fn with_span(self, span: f32) -> Self {
+		Self { span, ..self }
+	}
+
+	pub fn with_actuator(self, actuator: Actuator) -> Self {
+		Self { actuator, ..self }
+	}
+
+	/// Lift increase in full ground effect, as a fraction of the free-air lift.
+	pub const GROUND_EFFECT_LIFT: f32 = 0.5;
+
+	/// Ground effect at `height` (m) above the terrain: 0 high up, approaching 1 on the ground.
+	/// Induced drag is reduced by this fraction (McCormick), lift increased by `GROUND_EFFECT_LIFT` times it.
+	pub fn ground_effect(&self, height: f32) -> f32 {
+		if self.span <= 0.0 {
+			return 0.0;
+		}
+		let h = 16.0 * height.max(0.0) / self.span;
+		1.0 / (1.0 + h * h)
+	}
+
+	/// Deflection (rad) commanded by the pilot's inputs.
+	pub fn commanded_deflection(&self, inputs: &ControlInputs) -> f32 {
+		inputs.get(self.control) * self.max_deflection
+	}
+
+	/// Move the surface according to the pilot's inputs, instantly (as far as the actuator can reach).
+	pub fn apply_controls(&mut self, inputs: &ControlInputs, indicated_airspeed: f32) {
+		self.deflection = self.actuator.reachable(self.commanded_deflection(inputs), indicated_airspeed);
+	}
+
+	/// Move the surface towards the pilot's inputs at the actuator's pace, for `dt` seconds.
+	pub fn tick_actuator(&mut self, dt: f32, inputs: &ControlInputs, indicated_airspeed: f32) {
+		self.deflection = self.actuator.tick(dt, self.commanded_deflection(inputs), indicated_airspeed, self.deflection);
+	}
+}
+
+impl ControlInputs {
+	/// Brake input 0..1, including the parking brake.
+	pub fn brake_input(&self) -> f32 {
+		if self.parking_brake {
+			1.0
+		} else {
+			self.brakes.clamp(0.0, 1.0)
+		}
+	}
+
+	pub fn get(&self, axis: ControlAxis) -> f32 {
+		match axis {
+			ControlAxis::Fixed => 0.0,
+			ControlAxis::Pitch => (self.pitch + self.pitch_trim).clamp(-1.0, 1.0),
+			ControlAxis::Flaps => self.flaps.clamp(0.0, 1.0),
+			ControlAxis::Spoilers => self.spoilers.clamp(0.0, 1.0),
+		}
+	}
+}
+
+impl SurfaceKind {
+	pub const ALL: [Self; 6] = [Self::MainWing, Self::Flap, Self::Slat, Self::Canard, Self::HorizontalTail, Self::Spoiler];
+}
+
+impl ControlAxis {
+	pub const ALL: [Self; 4] = [Self::Fixed, Self::Pitch, Self::Flaps, Self::Spoilers];
+}
+
+impl Default for Winglet {
+	fn default() -> Self {
+		Self {
+			name: String::new(),
+			kind: SurfaceKind::MainWing,
+			pos: vec::ZERO,
+			pitch: 0.0,
+			curve: LiftCurve::with_stall(1.0, 15.0, 15.0 * DEG),
+			pitching_moment: 0.0,
+			control: ControlAxis::Fixed,
+			max_deflection: 0.0,
+			actuator: default(),
+			deflection: 0.0,
+			ground_offset: 0.0,
+			span: 0.0,
+			size: vec2(2.0, 1.0),
+		}
+	}
+}
+
+impl EguiInspect for SurfaceKind {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+
+	fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+		inspect_enum_mut(self, &Self::ALL, label, ui)
+	}
+}
+
+impl EguiInspect for ControlAxis {
+	fn inspect(&self, label: &str, ui: &mut egui::Ui) {
+		inspect_debug(self, label, ui)
+	}
+
+	fn inspect_mut(&mut self, label: &str, ui: &mut egui::Ui) {
+		inspect_enum_mut(self, &Self::ALL, label, ui)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn ground_effect_fades_with_height() {
+		let wing = Winglet::default().with_span(10.0);
+		assert!(wing.ground_effect(0.0) == 1.0);
+		assert!(wing.ground_effect(1.0) > 0.2);
+		assert!(wing.ground_effect(10.0) < 0.01);
+		assert_eq!(Winglet::default().ground_effect(0.0), 0.0);
+	}
+
+	#[test]
+	fn ground_effect_from_the_terrain_under_each_surface() {
+		let mut plane = Plane::default();
+		plane.body.position = vec2(100.0, 4.0);
+		let tail = plane.surfaces.iter().position(|s| s.kind == SurfaceKind::HorizontalTail).unwrap();
+		let tail_x = plane.body.transform_rel_pos(plane.surfaces[tail].pos).x();
+		let mut terrain = Tilemap::new(vec2(1024, 64), Tile::AIR);
+		plane.tick(0.001, &terrain, Integrator::RK4);
+		let flat = plane.winglet_ground_effect(&plane.surfaces[tail]);
+
+		// a bump under the tail, not under the wing
+		terrain.add_hill(tail_x, 1.0, 2.0);
+		plane.tick(0.001, &terrain, Integrator::RK4);
+		assert_eq!(plane.main_wing().unwrap().ground_offset, 0.0);
+		assert_eq!(plane.surfaces[tail].ground_offset, 2.0);
+		assert!(plane.winglet_ground_effect(&plane.surfaces[tail]) > 2.0 * flat, "{flat}");
+	}
 }
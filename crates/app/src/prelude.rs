@@ -14,7 +14,7 @@ pub use image::{DynamicImage, GenericImageView};
 pub use itertools::Itertools;
 pub use num_traits::AsPrimitive;
 pub use serde::{de::DeserializeOwned, Deserialize, Serialize};
-pub use egui_inspect::{inspect_debug, EguiInspect};
+pub use egui_inspect::{inspect_debug, inspect_enum_mut, EguiInspect};
 pub use flate2::read::GzDecoder;
 pub use flate2::write::GzEncoder;
 pub use web_time::{Duration, Instant, SystemTime};
@@ -25,6 +25,7 @@ pub type HashSet<T> = std::collections::HashSet<T, std::hash::BuildHasherDefault
 
 pub use std::cell::{Cell, RefCell};
 pub use std::cmp::{PartialEq, PartialOrd};
+pub use std::collections::BTreeMap;
 pub use std::collections::BinaryHeap;
 pub use std::collections::VecDeque;
 pub use std::f32::consts::PI;
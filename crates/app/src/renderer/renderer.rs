@@ -21,6 +21,7 @@ pub struct Renderer {
 
 pub const RED: vec4<u8> = vec4(255, 0, 0, 255);
 pub const BLUE: vec4<u8> = vec4(0, 0, 255, 255);
+pub const MAGENTA: vec4<u8> = vec4(255, 0, 255, 255);
 
 /// GPU buffers byte size is multiple of this.
 /// wgpu does not like arbitrarily sized buffers.
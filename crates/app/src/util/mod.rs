@@ -1,3 +1,5 @@
 mod bounds;
+mod rng;
 
 pub use bounds::*;
+pub use rng::*;
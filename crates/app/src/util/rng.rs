@@ -0,0 +1,44 @@
+use crate::prelude::*;
+
+/// Small, seedable pseudo-random number generator (xorshift64*).
+/// Deterministic across platforms so that a seed reproduces the same scenario.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Self {
+		// state must never be zero
+		Self {
+			state: (seed ^ 0x9E37_79B9_7F4A_7C15).max(1),
+		}
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		let mut x = self.state;
+		x ^= x >> 12;
+		x ^= x << 25;
+		x ^= x >> 27;
+		self.state = x;
+		x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+	}
+
+	/// Uniformly distributed in 0..1.
+	pub fn uniform(&mut self) -> f32 {
+		(self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+	}
+
+	/// Normally distributed with zero mean and unit variance (Box-Muller).
+	pub fn normal(&mut self) -> f32 {
+		let u1 = self.uniform().max(f32::MIN_POSITIVE);
+		let u2 = self.uniform();
+		(-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+	}
+}
+
+impl Default for Rng {
+	fn default() -> Self {
+		Self::new(1)
+	}
+}
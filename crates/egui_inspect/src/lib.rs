@@ -71,6 +71,15 @@ pub fn inspect_debug<T: std::fmt::Debug>(v: &T, label: &str, ui: &mut egui::Ui)
 	});
 }
 
+/// Combo box to select one of `options`, labeled with their `Debug` representation.
+pub fn inspect_enum_mut<T: std::fmt::Debug + PartialEq + Copy>(v: &mut T, options: &[T], label: &str, ui: &mut egui::Ui) {
+	egui::ComboBox::from_label(label).selected_text(format!("{v:?}")).show_ui(ui, |ui| {
+		for &option in options {
+			ui.selectable_value(v, option, format!("{option:?}"));
+		}
+	});
+}
+
 pub trait InspectNumber {
 	fn inspect_with_slider(&mut self, label: &str, ui: &mut egui::Ui, min: f32, max: f32);
 	fn inspect_with_drag_value(&mut self, label: &str, ui: &mut egui::Ui);